@@ -0,0 +1,166 @@
+//! Segmented output for long recordings: instead of one monolithic WAV,
+//! write fixed-duration segment files plus an `.m3u8` media playlist (the
+//! same shape a VOD audio rendition would use), so the UI can seek to any
+//! point and transcription can run per-segment — a crash partway through a
+//! multi-hour capture still leaves every already-rolled segment with a
+//! durable transcript instead of losing the whole session.
+//!
+//! Only the macOS backend uses this directly (`AudioRecorder` feeds it
+//! sample buffers as they arrive); the Linux backend gets the same shape
+//! for free by pointing ffmpeg at its own `segment` muxer instead.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use super::pcm::{self, WavSampleFormat};
+
+/// How much audio each segment covers before rolling over to the next file.
+pub const SEGMENT_SECS: f64 = 60.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HlsSegmentReadyEvent {
+    pub playlist_base: String,
+    pub index: u32,
+    pub path: String,
+    pub duration_secs: f64,
+}
+
+struct SegmentInfo {
+    file_name: String,
+    duration_secs: f64,
+}
+
+struct PendingSegment {
+    index: u32,
+    samples: Vec<f32>,
+}
+
+/// Accumulates interleaved float samples for the session currently being
+/// captured and rolls them over into `SEGMENT_SECS`-long WAV files. Safe to
+/// call `push` from the capture callback thread; `finalize` is called once,
+/// from `stop_capture`.
+pub struct HlsWriter {
+    app_handle: AppHandle,
+    dir: PathBuf,
+    base_name: String,
+    sample_rate: u32,
+    channels: u16,
+    wav_format: WavSampleFormat,
+    pending: Mutex<PendingSegment>,
+    completed: Mutex<Vec<SegmentInfo>>,
+}
+
+impl HlsWriter {
+    /// `output_base` is the same extension-less path the non-HLS path uses
+    /// (`pcm::generate_output_base`'s result); segments and the playlist are
+    /// written alongside it as `<base>_000.wav`, `<base>_001.wav`, ...,
+    /// `<base>.m3u8`.
+    pub fn new(app_handle: AppHandle, output_base: &str, sample_rate: u32, channels: u16, wav_format: WavSampleFormat) -> Self {
+        let path = Path::new(output_base);
+        let dir = path.parent().map(|p| p.to_path_buf()).unwrap_or_else(|| PathBuf::from("."));
+        let base_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "capture".to_string());
+
+        Self {
+            app_handle,
+            dir,
+            base_name,
+            sample_rate,
+            channels,
+            wav_format,
+            pending: Mutex::new(PendingSegment { index: 0, samples: Vec::new() }),
+            completed: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn segment_samples(&self) -> usize {
+        (SEGMENT_SECS * self.sample_rate as f64) as usize * self.channels as usize
+    }
+
+    fn segment_file_name(&self, index: u32) -> String {
+        format!("{}_{:03}.wav", self.base_name, index)
+    }
+
+    /// Append newly captured interleaved samples, rolling over to a new
+    /// segment file (and emitting `hls-segment-ready`) whenever the current
+    /// one reaches `SEGMENT_SECS`.
+    pub fn push(&self, samples: &[f32]) {
+        let Ok(mut pending) = self.pending.lock() else { return };
+        pending.samples.extend_from_slice(samples);
+
+        while pending.samples.len() >= self.segment_samples() {
+            let segment_len = self.segment_samples();
+            let segment_samples: Vec<f32> = pending.samples.drain(..segment_len).collect();
+            let index = pending.index;
+            pending.index += 1;
+            self.flush_segment(index, &segment_samples);
+        }
+    }
+
+    fn flush_segment(&self, index: u32, samples: &[f32]) {
+        let file_name = self.segment_file_name(index);
+        let path = self.dir.join(&file_name);
+        let duration_secs = samples.len() as f64 / self.channels as f64 / self.sample_rate as f64;
+
+        if let Err(e) = pcm::write_wav(&path.to_string_lossy(), samples, self.sample_rate, self.channels, self.wav_format) {
+            eprintln!("[Logia ERROR] Failed to write HLS segment {}: {}", file_name, e);
+            return;
+        }
+
+        if let Ok(mut completed) = self.completed.lock() {
+            completed.push(SegmentInfo { file_name: file_name.clone(), duration_secs });
+        }
+
+        use tauri::Emitter;
+        let _ = self.app_handle.emit(
+            "hls-segment-ready",
+            HlsSegmentReadyEvent {
+                playlist_base: self.base_name.clone(),
+                index,
+                path: path.to_string_lossy().to_string(),
+                duration_secs,
+            },
+        );
+    }
+
+    /// Flush whatever's left as a final (possibly shorter) segment, write
+    /// the `.m3u8` playlist listing every segment in order, and return the
+    /// playlist's path.
+    pub fn finalize(self) -> Result<String, String> {
+        let remaining = {
+            let mut pending = self.pending.lock().map_err(|e| format!("Mutex error: {}", e))?;
+            std::mem::take(&mut pending.samples)
+        };
+        if !remaining.is_empty() {
+            let index = self.pending.lock().map_err(|e| format!("Mutex error: {}", e))?.index;
+            self.flush_segment(index, &remaining);
+        }
+
+        let completed = self.completed.lock().map_err(|e| format!("Mutex error: {}", e))?;
+        if completed.is_empty() {
+            return Err("No audio segments were captured".to_string());
+        }
+
+        let target_duration = completed.iter().map(|s| s.duration_secs.ceil() as u32).max().unwrap_or(SEGMENT_SECS as u32);
+
+        let mut playlist = String::new();
+        playlist.push_str("#EXTM3U\n");
+        playlist.push_str("#EXT-X-VERSION:3\n");
+        playlist.push_str(&format!("#EXT-X-TARGETDURATION:{}\n", target_duration));
+        playlist.push_str("#EXT-X-MEDIA-SEQUENCE:0\n");
+        playlist.push_str("#EXT-X-PLAYLIST-TYPE:VOD\n");
+        for segment in completed.iter() {
+            playlist.push_str(&format!("#EXTINF:{:.3},\n", segment.duration_secs));
+            playlist.push_str(&segment.file_name);
+            playlist.push('\n');
+        }
+        playlist.push_str("#EXT-X-ENDLIST\n");
+
+        let playlist_path = self.dir.join(format!("{}.m3u8", self.base_name));
+        fs::write(&playlist_path, playlist).map_err(|e| format!("Failed to write playlist: {}", e))?;
+
+        Ok(playlist_path.to_string_lossy().to_string())
+    }
+}
@@ -3,8 +3,20 @@ use std::process::Command;
 use std::sync::{Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Manager};
+
+use crate::audio::{transcode::AudioCodec, CaptureMode};
+
 static CAPTURE_PROCESS: OnceLock<Mutex<Option<std::process::Child>>> = OnceLock::new();
 static CURRENT_FILE: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+/// Compressed container chosen at `start_capture`, if any; see the mac
+/// backend's `OUTPUT_FORMAT` for the full rationale — ffmpeg here still
+/// always writes a plain WAV, and `stop_capture` transcodes it afterward.
+static OUTPUT_FORMAT: OnceLock<Mutex<Option<AudioCodec>>> = OnceLock::new();
+/// Set when `start_capture` was asked for HLS segmenting; `CURRENT_FILE`
+/// then holds the `.m3u8` playlist path ffmpeg's own `segment` muxer wrote
+/// instead of a single WAV, and `stop_capture` hands it back unmodified
+/// (compression/validation are per-segment concerns, not wired up here yet).
+static CURRENT_IS_HLS: OnceLock<Mutex<bool>> = OnceLock::new();
 
 fn generate_output_file(app_handle: &AppHandle) -> Result<String, String> {
     let timestamp = SystemTime::now()
@@ -48,37 +60,97 @@ fn get_default_sink() -> Result<String, String> {
 }
 
 pub fn start_capture(app_handle: &AppHandle) -> Result<(), String> {
-    println!("Starting audio capture on Linux");
-    eprintln!("[Logia DEBUG] Starting audio capture on Linux");
-    
-    let default_sink = get_default_sink().map_err(|e| {
-        eprintln!("[Logia ERROR] Failed to get default sink: {}", e);
-        e
-    })?;
-    eprintln!("[Logia DEBUG] Got default sink: {}", default_sink);
-    
-    let monitor_name = format!("{}.monitor", default_sink);
-    eprintln!("[Logia DEBUG] Monitor name: {}", monitor_name);
-    
-    let output_file = generate_output_file(app_handle)?;
+    start_capture_with_mode(app_handle, CaptureMode::SystemOnly, None, false)
+}
+
+pub fn start_capture_with_mode(app_handle: &AppHandle, mode: CaptureMode, format: Option<AudioCodec>, hls: bool) -> Result<(), String> {
+    println!("Starting audio capture on Linux, mode={:?}, format={:?}, hls={}", mode, format, hls);
+    eprintln!("[Logia DEBUG] Starting audio capture on Linux, mode={:?}, format={:?}, hls={}", mode, format, hls);
+
+    let format_mutex = OUTPUT_FORMAT.get_or_init(|| Mutex::new(None));
+    *format_mutex.lock().map_err(|e| format!("Mutex error:{}", e))? = format;
+
+    let hls_mutex = CURRENT_IS_HLS.get_or_init(|| Mutex::new(false));
+    *hls_mutex.lock().map_err(|e| format!("Mutex error:{}", e))? = hls;
+
+    // ffmpeg's own `segment` muxer gives us the same shape as the mac
+    // backend's hand-rolled `HlsWriter` (fixed-duration WAV segments plus an
+    // `.m3u8` playlist) for free, since ffmpeg is already in the critical
+    // path here — no need to buffer and roll segments ourselves.
+    let output_base = if hls { Some(crate::audio::pcm::generate_output_base(app_handle)?) } else { None };
+    let output_file = match &output_base {
+        Some(base) => format!("{}.m3u8", base),
+        None => generate_output_file(app_handle)?,
+    };
     eprintln!("[Logia DEBUG] Output file: {}", output_file);
 
+    // Mic input is just PulseAudio's default *source*; system output is
+    // captured via the default sink's *monitor* source. `amix` combines both
+    // when both are requested, matching how the other backends mix in a mic
+    // track under the system audio instead of replacing it.
+    let mic_source = "default".to_string();
+    let monitor_source = if mode.wants_system() {
+        let default_sink = get_default_sink().map_err(|e| {
+            eprintln!("[Logia ERROR] Failed to get default sink: {}", e);
+            e
+        })?;
+        let monitor_name = format!("{}.monitor", default_sink);
+        eprintln!("[Logia DEBUG] Monitor name: {}", monitor_name);
+        Some(monitor_name)
+    } else {
+        None
+    };
+
     let mut cmd = Command::new("ffmpeg");
+
+    match (mode.wants_system(), mode.wants_mic()) {
+        (true, true) => {
+            cmd.args(&[
+                "-f", "pulse", "-i", monitor_source.as_deref().unwrap(),
+                "-f", "pulse", "-i", &mic_source,
+                "-filter_complex", "amix=inputs=2:duration=longest",
+            ]);
+        }
+        (true, false) => {
+            cmd.args(&["-f", "pulse", "-i", monitor_source.as_deref().unwrap()]);
+        }
+        (false, true) => {
+            cmd.args(&["-f", "pulse", "-i", &mic_source]);
+        }
+        (false, false) => unreachable!("CaptureMode always wants at least one source"),
+    }
+
     cmd.args(&[
-        "-f",
-        "pulse", // PulseAudio input format
-        "-i",
-        &monitor_name, // Input from default sink's monitor
         "-acodec",
         "pcm_s16le", // 16-bit little-endian PCM
         "-ar",
         "16000", // Sample rate (good for speech)
         "-ac",
-        "1",          // Mono audio
-        "-y",         // Overwrite output file if exists
-        &output_file, // Output filename
+        "1", // Mono audio
     ]);
 
+    if let Some(base) = &output_base {
+        // Segment muxer: fixed-duration WAV files plus a self-maintained
+        // `.m3u8` playlist, matching `audio::hls::SEGMENT_SECS`.
+        let segment_pattern = format!("{}_%03d.wav", base);
+        cmd.args(&[
+            "-f",
+            "segment",
+            "-segment_time",
+            &crate::audio::hls::SEGMENT_SECS.to_string(),
+            "-segment_list",
+            &output_file,
+            "-segment_list_type",
+            "m3u8",
+            "-reset_timestamps",
+            "1",
+            "-y",
+            &segment_pattern,
+        ]);
+    } else {
+        cmd.args(&["-y", &output_file]);
+    }
+
     let child = cmd
         .spawn()
         .map_err(|e| {
@@ -100,12 +172,15 @@ pub fn start_capture(app_handle: &AppHandle) -> Result<(), String> {
         .map_err(|e| format!("Mutex error:{}", e))?;
     *file_guard = Some(output_file.clone());
 
-    println!("Capturing audio from '{}' to {}", monitor_name, output_file);
+    println!(
+        "Capturing audio (system={:?}, mic={}) to {}",
+        monitor_source, mode.wants_mic(), output_file
+    );
     eprintln!("[Logia DEBUG] Capture started successfully");
     Ok(())
 }
 
-pub fn stop_capture() -> Result<String, String> {
+pub fn stop_capture(app_handle: &AppHandle) -> Result<String, String> {
     let process_mutex = CAPTURE_PROCESS
         .get()
         .ok_or("Capture process not initialized")?;
@@ -140,11 +215,21 @@ pub fn stop_capture() -> Result<String, String> {
         .map_err(|e| format!("Mutex error:{}", e))?;
     let file_path = file_guard.take().ok_or("No file path stored")?;
 
-    Ok(file_path)
+    let is_hls = CURRENT_IS_HLS.get().and_then(|m| m.lock().ok()).map(|g| *g).unwrap_or(false);
+    if is_hls {
+        // The playlist ffmpeg wrote already names every finished segment;
+        // there's no single WAV here to validate or transcode.
+        return Ok(file_path);
+    }
+
+    crate::audio::wav_validation::validate_wav_file(app_handle, &file_path)?;
+
+    let format = OUTPUT_FORMAT.get().and_then(|m| m.lock().ok()).and_then(|mut g| g.take());
+    Ok(crate::audio::transcode::transcode_if_requested(&file_path, format))
 }
 
-pub fn cleanup() -> Result<(), String> {
-    stop_capture();
+pub fn cleanup(app_handle: &AppHandle) -> Result<(), String> {
+    let _ = stop_capture(app_handle);
     println!("Cleanup done");
     Ok(())
 }
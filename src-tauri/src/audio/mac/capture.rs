@@ -3,6 +3,14 @@ use std::io::{Write, Read, Seek, SeekFrom};
 use std::sync::{Arc, Mutex, OnceLock};
 use tauri::{AppHandle, Manager};
 
+use crate::audio::{hls::HlsWriter, mic::MicCapture, pcm, streaming::StreamingTranscriber, transcode::AudioCodec, CaptureMode};
+
+// System audio from ScreenCaptureKit is always 32-bit float, interleaved
+// stereo at this rate — both the monolithic-WAV path and the HLS segment
+// path build on it.
+const SAMPLE_RATE: u32 = 48000;
+const CHANNELS: u16 = 2;
+
 // Note: These imports assume screencapturekit 1.4.2+ API structure.
 #[cfg(target_os = "macos")]
 use screencapturekit::prelude::*;
@@ -11,11 +19,31 @@ use screencapturekit::prelude::*;
 #[cfg(target_os = "macos")]
 static CAPTURE_STREAM: OnceLock<Mutex<Option<SCStream>>> = OnceLock::new();
 static OUTPUT_FILE_PATH: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+/// Set whenever `start_capture` was asked for the microphone; taken and
+/// stopped by `stop_capture` so its track can be mixed into the final WAV
+/// (in HLS mode it is stopped without being mixed in).
+static MIC_CAPTURE: OnceLock<Mutex<Option<MicCapture>>> = OnceLock::new();
+/// Compressed container chosen at `start_capture`, if any; consumed by
+/// `stop_capture` to transcode the captured WAV before handing its path
+/// back. `None` keeps the plain-WAV behavior callers already depend on.
+static OUTPUT_FORMAT: OnceLock<Mutex<Option<AudioCodec>>> = OnceLock::new();
+/// Live captioning worker for the current session, if any. Fed sample
+/// buffers directly from `AudioRecorder::did_output_sample_buffer` as they
+/// arrive, independently of the raw PCM file `AudioRecorder` also writes.
+static STREAMING: OnceLock<Mutex<Option<Arc<StreamingTranscriber>>>> = OnceLock::new();
+/// Segmented-output writer for the current session, if HLS mode was
+/// requested at `start_capture`. When present, `AudioRecorder` skips the
+/// monolithic `.pcm` file entirely and feeds raw interleaved stereo samples
+/// here instead; `stop_capture` finalizes it into an `.m3u8` playlist rather
+/// than building a single WAV.
+static HLS_WRITER: OnceLock<Mutex<Option<Arc<HlsWriter>>>> = OnceLock::new();
 
 #[cfg(target_os = "macos")]
 struct AudioRecorder {
-    file: Arc<Mutex<File>>,
+    file: Option<Arc<Mutex<File>>>,
     bytes_written: Arc<Mutex<u64>>,
+    streaming: Option<Arc<StreamingTranscriber>>,
+    hls: Option<Arc<HlsWriter>>,
 }
 
 #[cfg(target_os = "macos")]
@@ -28,13 +56,35 @@ impl SCStreamOutputTrait for AudioRecorder {
                 for audio_buffer in audio_buffer_list.iter() {
                     let data = audio_buffer.data();
                     if !data.is_empty() {
-                        if let Ok(mut file) = self.file.lock() {
-                            if let Ok(_) = file.write_all(data) {
-                                if let Ok(mut bytes) = self.bytes_written.lock() {
-                                    *bytes += data.len() as u64;
+                        if let Some(file) = &self.file {
+                            if let Ok(mut file) = file.lock() {
+                                if let Ok(_) = file.write_all(data) {
+                                    if let Ok(mut bytes) = self.bytes_written.lock() {
+                                        *bytes += data.len() as u64;
+                                    }
                                 }
                             }
                         }
+
+                        let floats: Vec<f32> = data
+                            .chunks_exact(4)
+                            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                            .collect();
+
+                        if let Some(hls) = &self.hls {
+                            // HLS segments are written in the same raw
+                            // interleaved stereo layout as the monolithic
+                            // WAV path, so a segment stands on its own.
+                            hls.push(&floats);
+                        }
+
+                        if let Some(streaming) = &self.streaming {
+                            // Downmix to mono before handing it to the
+                            // transcriber, which doesn't care about stereo
+                            // positioning.
+                            let mono: Vec<f32> = floats.chunks(2).map(|frame| frame.iter().sum::<f32>() / frame.len() as f32).collect();
+                            streaming.push(&mono);
+                        }
                     }
                 }
             }
@@ -42,52 +92,23 @@ impl SCStreamOutputTrait for AudioRecorder {
     }
 }
 
-fn generate_output_file(app_handle: &AppHandle) -> Result<String, String> {
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    let file_name = format!("capture_{}", timestamp);
-
-    let audio_dir = app_handle
-        .path()
-        .app_data_dir()
-        .unwrap()
-        .join("audio");
-    std::fs::create_dir_all(&audio_dir)
-        .map_err(|e| format!("Failed to create audio directory: {}", e))?;
-
-    Ok(audio_dir.join(file_name).to_string_lossy().to_string())
-}
-
-fn create_wav_header(data_size: u32, sample_rate: u32, channels: u16, bits_per_sample: u16) -> Vec<u8> {
-    let byte_rate = sample_rate * (channels as u32) * (bits_per_sample as u32) / 8;
-    let block_align = channels * bits_per_sample / 8;
-    
-    let mut header = Vec::with_capacity(44);
-    header.extend_from_slice(b"RIFF");
-    header.extend_from_slice(&(data_size + 36).to_le_bytes());
-    header.extend_from_slice(b"WAVE");
-    header.extend_from_slice(b"fmt ");
-    header.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
-    header.extend_from_slice(&1u16.to_le_bytes());  // PCM format
-    header.extend_from_slice(&channels.to_le_bytes());
-    header.extend_from_slice(&sample_rate.to_le_bytes());
-    header.extend_from_slice(&byte_rate.to_le_bytes());
-    header.extend_from_slice(&block_align.to_le_bytes());
-    header.extend_from_slice(&bits_per_sample.to_le_bytes());
-    header.extend_from_slice(b"data");
-    header.extend_from_slice(&data_size.to_le_bytes());
-    
-    header
+#[cfg(target_os = "macos")]
+pub fn start_capture(app_handle: &AppHandle) -> Result<(), String> {
+    start_capture_with_mode(app_handle, CaptureMode::SystemOnly, None, false)
 }
 
 #[cfg(target_os = "macos")]
-pub fn start_capture(app_handle: &AppHandle) -> Result<(), String> {
+pub fn start_capture_with_mode(app_handle: &AppHandle, mode: CaptureMode, format: Option<AudioCodec>, hls: bool) -> Result<(), String> {
     println!("Starting audio capture (screencapturekit)");
-    eprintln!("[Logia DEBUG] Starting macOS audio capture with ScreenCaptureKit");
+    eprintln!("[Logia DEBUG] Starting macOS audio capture with ScreenCaptureKit, mode={:?}, format={:?}, hls={}", mode, format, hls);
+
+    let format_mutex = OUTPUT_FORMAT.get_or_init(|| Mutex::new(None));
+    match format_mutex.lock() {
+        Ok(mut guard) => *guard = format,
+        Err(e) => return Err(format!("Mutex error: {}", e)),
+    }
 
-    match start_capture_inner(app_handle) {
+    match start_capture_inner(app_handle, mode, hls) {
         Ok(_) => {
             eprintln!("[Logia DEBUG] Audio capture started successfully");
             Ok(())
@@ -100,14 +121,51 @@ pub fn start_capture(app_handle: &AppHandle) -> Result<(), String> {
 }
 
 #[cfg(target_os = "macos")]
-fn start_capture_inner(app_handle: &AppHandle) -> Result<(), String> {
+fn start_capture_inner(app_handle: &AppHandle, mode: CaptureMode, hls: bool) -> Result<(), String> {
     // 1. Setup Output File
-    let output_base = generate_output_file(app_handle)?;
+    let output_base = pcm::generate_output_base(app_handle)?;
     let pcm_path = format!("{}.pcm", output_base);
     eprintln!("[Logia DEBUG] Output PCM file: {}", pcm_path);
-    
-    let file = File::create(&pcm_path).map_err(|e| format!("Failed to create PCM file: {}", e))?;
-    let file_arc = Arc::new(Mutex::new(file));
+
+    if mode.wants_mic() {
+        eprintln!("[Logia DEBUG] Starting microphone capture alongside system audio");
+        let mic = MicCapture::start()?;
+        let mic_mutex = MIC_CAPTURE.get_or_init(|| Mutex::new(None));
+        *mic_mutex.lock().map_err(|e| format!("Mutex error: {}", e))? = Some(mic);
+    }
+
+    if !mode.wants_system() {
+        // Mic-only: there's no ScreenCaptureKit stream to start, just record
+        // the output path so `stop_capture` knows where to land the WAV.
+        let path_mutex = OUTPUT_FILE_PATH.get_or_init(|| Mutex::new(None));
+        *path_mutex.lock().map_err(|e| format!("Mutex error: {}", e))? = Some(output_base);
+        return Ok(());
+    }
+
+    // HLS mode segments system audio directly as it arrives, so there's no
+    // need for the monolithic `.pcm` scratch file `AudioRecorder` otherwise
+    // writes — `file` stays `None` and only the segment writer gets fed.
+    let hls_writer = if hls {
+        let writer = Arc::new(HlsWriter::new(
+            app_handle.clone(),
+            &output_base,
+            SAMPLE_RATE,
+            CHANNELS,
+            pcm::load_wav_format_settings(app_handle),
+        ));
+        let hls_mutex = HLS_WRITER.get_or_init(|| Mutex::new(None));
+        *hls_mutex.lock().map_err(|e| format!("Mutex error: {}", e))? = Some(writer.clone());
+        Some(writer)
+    } else {
+        None
+    };
+
+    let file_arc = if hls_writer.is_some() {
+        None
+    } else {
+        let file = File::create(&pcm_path).map_err(|e| format!("Failed to create PCM file: {}", e))?;
+        Some(Arc::new(Mutex::new(file)))
+    };
     let bytes_written = Arc::new(Mutex::new(0u64));
 
     // 2. Setup ScreenCaptureKit
@@ -143,12 +201,22 @@ fn start_capture_inner(app_handle: &AppHandle) -> Result<(), String> {
         .with_sample_rate(48000)  // 48kHz sample rate
         .with_channel_count(2);   // Stereo
 
+    // Live captioning worker: transcribes ~20s windows as they arrive so the
+    // frontend can show captions during capture instead of only after
+    // `stop_capture`. Runs off the same 48kHz stream the PCM file is built
+    // from.
+    let streaming = StreamingTranscriber::start(app_handle.clone(), 48000);
+    let streaming_mutex = STREAMING.get_or_init(|| Mutex::new(None));
+    *streaming_mutex.lock().map_err(|e| format!("Mutex error: {}", e))? = Some(streaming.clone());
+
     // Output Handler
-    let recorder = AudioRecorder { 
+    let recorder = AudioRecorder {
         file: file_arc,
         bytes_written: bytes_written.clone(),
+        streaming: Some(streaming),
+        hls: hls_writer,
     };
-    
+
     // Stream - add handler for audio output type
     let mut stream = SCStream::new(&filter, &config);
     stream.add_output_handler(recorder, SCStreamOutputType::Audio);
@@ -177,21 +245,122 @@ pub fn start_capture(_app_handle: &AppHandle) -> Result<(), String> {
     Err("Audio capture is only supported on macOS".to_string())
 }
 
+#[cfg(not(target_os = "macos"))]
+pub fn start_capture_with_mode(_app_handle: &AppHandle, _mode: CaptureMode, _format: Option<AudioCodec>, _hls: bool) -> Result<(), String> {
+    Err("Audio capture is only supported on macOS".to_string())
+}
+
+/// Stop whatever mic stream is running and return its track, resampled to
+/// `target_rate`/`target_channels` if `stop_capture` also has a system track
+/// to mix it against. A bare copy-through (no resampling) when the rates
+/// already match covers the common case without pulling in a resampler.
+fn take_mic_track(target_rate: u32, target_channels: u16) -> Option<Vec<f32>> {
+    let mic_mutex = MIC_CAPTURE.get()?;
+    let mic = mic_mutex.lock().ok()?.take()?;
+    let (samples, rate, channels) = mic.stop();
+
+    if rate == target_rate && channels == target_channels {
+        return Some(samples);
+    }
+
+    eprintln!(
+        "[Logia DEBUG] Mic track is {}Hz/{}ch, resampling to {}Hz/{}ch to match system track",
+        rate, channels, target_rate, target_channels
+    );
+    Some(resample_linear(&samples, rate, channels, target_rate, target_channels))
+}
+
+/// Naive linear-interpolation resampler, good enough for mixing a voice
+/// track under system audio — not suitable for archival-quality resampling,
+/// but avoids pulling in a dedicated DSP crate for this one mixing step.
+fn resample_linear(samples: &[f32], from_rate: u32, from_channels: u16, to_rate: u32, to_channels: u16) -> Vec<f32> {
+    if samples.is_empty() || from_rate == 0 {
+        return Vec::new();
+    }
+
+    // Downmix/upmix channel count first by averaging or duplicating frames.
+    let mono: Vec<f32> = if from_channels <= 1 {
+        samples.to_vec()
+    } else {
+        samples
+            .chunks(from_channels as usize)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect()
+    };
+
+    let ratio = to_rate as f64 / from_rate as f64;
+    let out_len = ((mono.len() as f64) * ratio).round() as usize;
+    let mut resampled = Vec::with_capacity(out_len);
+    for i in 0..out_len {
+        let src_pos = i as f64 / ratio;
+        let idx = src_pos.floor() as usize;
+        let frac = (src_pos - idx as f64) as f32;
+        let a = mono.get(idx).copied().unwrap_or(0.0);
+        let b = mono.get(idx + 1).copied().unwrap_or(a);
+        resampled.push(a + (b - a) * frac);
+    }
+
+    if to_channels <= 1 {
+        resampled
+    } else {
+        let mut interleaved = Vec::with_capacity(resampled.len() * to_channels as usize);
+        for s in resampled {
+            for _ in 0..to_channels {
+                interleaved.push(s);
+            }
+        }
+        interleaved
+    }
+}
+
 #[cfg(target_os = "macos")]
-pub fn stop_capture() -> Result<String, String> {
+pub fn stop_capture(app_handle: &AppHandle) -> Result<String, String> {
     eprintln!("[Logia DEBUG] Stopping audio capture...");
-    
-    let state_mutex = CAPTURE_STREAM.get().ok_or("Capture not started")?;
-    let mut guard = state_mutex.lock().map_err(|e| format!("Mutex error: {}", e))?;
 
-    if let Some(stream) = guard.take() {
-        stream.stop_capture().map_err(|e| {
-            eprintln!("[Logia ERROR] stop_capture() failed: {:?}", e);
-            format!("Failed to stop capture: {:?}", e)
-        })?;
-        eprintln!("[Logia DEBUG] Capture stream stopped");
-    } else {
-        return Err("Capture not running".to_string());
+    // Flush and shut down the live captioning worker, if one was running
+    // (a mic-only session never starts one).
+    if let Some(streaming_mutex) = STREAMING.get() {
+        if let Ok(mut guard) = streaming_mutex.lock() {
+            if let Some(streaming) = guard.take() {
+                streaming.stop();
+            }
+        }
+    }
+
+    // A mic-only session never stores a stream in `CAPTURE_STREAM`, so an
+    // empty (or never-initialized) slot with a mic capture pending just
+    // means there's nothing system-side to stop here.
+    if let Some(state_mutex) = CAPTURE_STREAM.get() {
+        let mut guard = state_mutex.lock().map_err(|e| format!("Mutex error: {}", e))?;
+
+        if let Some(stream) = guard.take() {
+            stream.stop_capture().map_err(|e| {
+                eprintln!("[Logia ERROR] stop_capture() failed: {:?}", e);
+                format!("Failed to stop capture: {:?}", e)
+            })?;
+            eprintln!("[Logia DEBUG] Capture stream stopped");
+        } else if MIC_CAPTURE.get().and_then(|m| m.lock().ok()).map_or(true, |g| g.is_none()) {
+            return Err("Capture not running".to_string());
+        }
+    }
+
+    // HLS mode already wrote each segment as it arrived; finalizing just
+    // flushes whatever's left and writes the playlist, bypassing the
+    // monolithic-WAV path entirely (there's no `.pcm` file to convert).
+    if let Some(hls_mutex) = HLS_WRITER.get() {
+        if let Some(writer) = hls_mutex.lock().map_err(|e| format!("Mutex error: {}", e))?.take() {
+            let path_mutex = OUTPUT_FILE_PATH.get().ok_or("Output path lost")?;
+            path_mutex.lock().map_err(|e| format!("Mutex error: {}", e))?.take();
+
+            // Segments hold the system track only; a running mic stream is
+            // stopped here and its samples are discarded.
+            let _ = take_mic_track(SAMPLE_RATE, CHANNELS);
+
+            return match Arc::try_unwrap(writer) {
+                Ok(writer) => writer.finalize(),
+                Err(_) => Err("HLS writer still in use by the capture callback".to_string()),
+            };
+        }
     }
 
     // Convert PCM to WAV
@@ -201,57 +370,52 @@ pub fn stop_capture() -> Result<String, String> {
 
     let pcm_path = format!("{}.pcm", output_base);
     let wav_path = format!("{}.wav", output_base);
-    
+
     eprintln!("[Logia DEBUG] Converting PCM to WAV: {} -> {}", pcm_path, wav_path);
 
-    // Read PCM data
-    let mut pcm_file = File::open(&pcm_path).map_err(|e| format!("Failed to open PCM: {}", e))?;
-    let mut pcm_data = Vec::new();
-    pcm_file.read_to_end(&mut pcm_data).map_err(|e| format!("Failed to read PCM: {}", e))?;
-    
-    eprintln!("[Logia DEBUG] Read {} bytes of PCM data", pcm_data.len());
+    // A mic-only session never created this file at all.
+    let system_floats: Vec<f32> = match File::open(&pcm_path) {
+        Ok(mut pcm_file) => {
+            let mut pcm_data = Vec::new();
+            pcm_file.read_to_end(&mut pcm_data).map_err(|e| format!("Failed to read PCM: {}", e))?;
+            eprintln!("[Logia DEBUG] Read {} bytes of PCM data", pcm_data.len());
+            let _ = std::fs::remove_file(&pcm_path);
+            pcm_data
+                .chunks_exact(4)
+                .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                .collect()
+        }
+        Err(_) => Vec::new(),
+    };
 
-    if pcm_data.is_empty() {
-        // Clean up and return error
-        let _ = std::fs::remove_file(&pcm_path);
-        return Err("No audio data captured. Make sure system audio is playing.".to_string());
-    }
+    let mic_floats = take_mic_track(SAMPLE_RATE, CHANNELS);
 
-    // System audio from ScreenCaptureKit is 32-bit float, need to convert to 16-bit PCM for WAV
-    // However, the format depends on the ScreenCaptureKit configuration
-    // The raw bytes are interleaved stereo 32-bit float at 48kHz
-    
-    // Convert Float32 to Int16 for standard WAV compatibility
-    let sample_count = pcm_data.len() / 4; // 4 bytes per float32 sample
-    let mut int16_data = Vec::with_capacity(sample_count * 2);
-    
-    for chunk in pcm_data.chunks_exact(4) {
-        let float_sample = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
-        // Clamp and convert to i16 range
-        let clamped = float_sample.clamp(-1.0, 1.0);
-        let int16_sample = (clamped * 32767.0) as i16;
-        int16_data.extend_from_slice(&int16_sample.to_le_bytes());
-    }
-    
-    eprintln!("[Logia DEBUG] Converted to {} bytes of 16-bit PCM", int16_data.len());
+    let mixed_floats = match (system_floats.is_empty(), mic_floats) {
+        (false, Some(mic)) => {
+            eprintln!("[Logia DEBUG] Mixing {} system samples with {} mic samples", system_floats.len(), mic.len());
+            pcm::mix_tracks(&system_floats, &mic)
+        }
+        (false, None) => system_floats,
+        (true, Some(mic)) => mic,
+        (true, None) => {
+            return Err("No audio data captured. Make sure system audio is playing or a microphone is connected.".to_string());
+        }
+    };
 
-    // Create WAV file with appropriate header
-    // 48000 Hz, Stereo, 16-bit
-    let header = create_wav_header(int16_data.len() as u32, 48000, 2, 16);
-    let mut wav_file = File::create(&wav_path).map_err(|e| format!("Failed to create WAV: {}", e))?;
-    
-    wav_file.write_all(&header).map_err(|e| format!("Write header failed: {}", e))?;
-    wav_file.write_all(&int16_data).map_err(|e| format!("Write data failed: {}", e))?;
+    let wav_format = pcm::load_wav_format_settings(app_handle);
+    eprintln!("[Logia DEBUG] Writing {} samples as {:?} WAV", mixed_floats.len(), wav_format);
+
+    pcm::write_wav(&wav_path, &mixed_floats, SAMPLE_RATE, CHANNELS, wav_format)?;
 
-    // Clean up PCM file
-    let _ = std::fs::remove_file(&pcm_path);
-    
     eprintln!("[Logia DEBUG] WAV file created successfully: {}", wav_path);
 
-    Ok(wav_path)
+    crate::audio::wav_validation::validate_wav_file(app_handle, &wav_path)?;
+
+    let format = OUTPUT_FORMAT.get().and_then(|m| m.lock().ok()).and_then(|mut g| g.take());
+    Ok(crate::audio::transcode::transcode_if_requested(&wav_path, format))
 }
 
 #[cfg(not(target_os = "macos"))]
-pub fn stop_capture() -> Result<String, String> {
+pub fn stop_capture(_app_handle: &AppHandle) -> Result<String, String> {
     Err("Not supported".to_string())
 }
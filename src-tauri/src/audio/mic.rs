@@ -0,0 +1,83 @@
+//! Cross-platform microphone input, built on cpal so it works the same way
+//! on every OS `os_capture`'s system-audio path is platform-specific for —
+//! macOS's ScreenCaptureKit, Linux's pactl/ffmpeg pipeline and the Windows
+//! sidecar binary all capture the *output* device differently, but the
+//! user's own voice always comes in through the same kind of input stream.
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use std::sync::{Arc, Mutex};
+
+/// A running microphone input stream, accumulating interleaved f32 samples
+/// into a shared buffer until `stop` is called.
+pub struct MicCapture {
+    stream: cpal::Stream,
+    buffer: Arc<Mutex<Vec<f32>>>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl MicCapture {
+    /// Open the system default input device and start streaming into an
+    /// in-memory buffer. The stream itself runs on cpal's own audio thread;
+    /// `buffer` is only ever touched from the input callback and from
+    /// `stop`, both guarded by the same mutex.
+    pub fn start() -> Result<Self, String> {
+        let host = cpal::default_host();
+        let device = host
+            .default_input_device()
+            .ok_or("No default microphone input device found")?;
+
+        let config = device
+            .default_input_config()
+            .map_err(|e| format!("Failed to get default mic input config: {}", e))?;
+
+        let sample_rate = config.sample_rate().0;
+        let channels = config.channels();
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let buffer_cb = buffer.clone();
+
+        let err_fn = |err| eprintln!("[Logia ERROR] Microphone input stream error: {}", err);
+
+        let stream = match config.sample_format() {
+            cpal::SampleFormat::F32 => device
+                .build_input_stream(
+                    &config.into(),
+                    move |data: &[f32], _| {
+                        if let Ok(mut buf) = buffer_cb.lock() {
+                            buf.extend_from_slice(data);
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(|e| format!("Failed to build mic input stream: {}", e))?,
+            cpal::SampleFormat::I16 => device
+                .build_input_stream(
+                    &config.into(),
+                    move |data: &[i16], _| {
+                        if let Ok(mut buf) = buffer_cb.lock() {
+                            buf.extend(data.iter().map(|s| *s as f32 / 32768.0));
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(|e| format!("Failed to build mic input stream: {}", e))?,
+            other => return Err(format!("Unsupported mic sample format: {:?}", other)),
+        };
+
+        stream
+            .play()
+            .map_err(|e| format!("Failed to start mic input stream: {}", e))?;
+
+        Ok(Self { stream, buffer, sample_rate, channels })
+    }
+
+    /// Stop the stream and hand back everything captured, plus the format it
+    /// was captured in so the caller can resample/mix correctly.
+    pub fn stop(self) -> (Vec<f32>, u32, u16) {
+        drop(self.stream);
+        let samples = self.buffer.lock().map(|b| b.clone()).unwrap_or_default();
+        (samples, self.sample_rate, self.channels)
+    }
+}
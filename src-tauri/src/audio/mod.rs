@@ -1,4 +1,36 @@
+pub mod transcode;
 pub mod transcription;
+pub mod wav_validation;
+pub mod pcm;
+pub mod mic;
+pub mod streaming;
+pub mod hls;
+
+use serde::{Deserialize, Serialize};
+
+/// Which source(s) `start_capture` should record. All three `os_capture`
+/// backends accept this. When it asks for the microphone, macOS layers a
+/// [`mic::MicCapture`] on top of its system capture, Linux adds PulseAudio's
+/// default source to the ffmpeg command, and Windows passes the mode on to
+/// its capture sidecar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureMode {
+    #[default]
+    SystemOnly,
+    MicOnly,
+    Both,
+}
+
+impl CaptureMode {
+    pub fn wants_system(&self) -> bool {
+        matches!(self, CaptureMode::SystemOnly | CaptureMode::Both)
+    }
+
+    pub fn wants_mic(&self) -> bool {
+        matches!(self, CaptureMode::MicOnly | CaptureMode::Both)
+    }
+}
 
 #[cfg(target_os = "linux")]
 pub mod linux;
@@ -0,0 +1,142 @@
+//! WAV and output-path helpers for the capture backends. The macOS backend
+//! (and its HLS segment writer) writes every WAV through here; the Linux
+//! backend uses `generate_output_base` only for HLS output, and the Linux
+//! and Windows backends otherwise name their WAV files themselves. WAV reading and
+//! writing both go through `hound` rather than a hand-rolled RIFF walker,
+//! since a file this module didn't produce itself (a user pointing
+//! `transcribe_audio` at an external recording, say) can't be trusted to
+//! match the one 44-byte-header shape the old code assumed.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use crate::get_config_directory;
+
+/// Build the extension-less output path `<app-data>/audio/capture_<unix-ts>`
+/// that each backend appends `.pcm`/`.wav` (and friends) onto.
+pub fn generate_output_base(app_handle: &AppHandle) -> Result<String, String> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let file_name = format!("capture_{}", timestamp);
+
+    let audio_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("audio");
+    std::fs::create_dir_all(&audio_dir)
+        .map_err(|e| format!("Failed to create audio directory: {}", e))?;
+
+    Ok(audio_dir.join(file_name).to_string_lossy().to_string())
+}
+
+/// Bit depth/encoding to write captured audio as. `Pcm16` matches the old
+/// hand-rolled writer's behavior and stays the default; `Pcm24` keeps more
+/// headroom before clipping; `Float32` losslessly stores exactly what
+/// ScreenCaptureKit (or any other float-native capture backend) delivers,
+/// at the cost of roughly double the file size of `Pcm16`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WavSampleFormat {
+    #[default]
+    Pcm16,
+    Pcm24,
+    Float32,
+}
+
+/// Read `audio_wav_format` out of `config.json`, same convention as
+/// `transcode::load_transcode_settings`. Defaults to `Pcm16` so capture
+/// behavior doesn't change for anyone who hasn't opted in.
+pub fn load_wav_format_settings(app_handle: &AppHandle) -> WavSampleFormat {
+    let Ok(config_dir) = get_config_directory(app_handle) else { return WavSampleFormat::default() };
+    let config_file = config_dir.join("config.json");
+    let Ok(content) = fs::read_to_string(&config_file) else { return WavSampleFormat::default() };
+    let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) else { return WavSampleFormat::default() };
+
+    config
+        .get("audio_wav_format")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Write interleaved float32 `samples` (range roughly -1.0..=1.0) as a WAV
+/// file at `path`, in `format`'s bit depth/encoding.
+pub fn write_wav(path: &str, samples: &[f32], sample_rate: u32, channels: u16, format: WavSampleFormat) -> Result<(), String> {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: match format {
+            WavSampleFormat::Pcm16 => 16,
+            WavSampleFormat::Pcm24 => 24,
+            WavSampleFormat::Float32 => 32,
+        },
+        sample_format: match format {
+            WavSampleFormat::Float32 => hound::SampleFormat::Float,
+            WavSampleFormat::Pcm16 | WavSampleFormat::Pcm24 => hound::SampleFormat::Int,
+        },
+    };
+
+    let mut writer = hound::WavWriter::create(path, spec).map_err(|e| format!("Failed to create WAV writer: {}", e))?;
+
+    for &s in samples {
+        let result = match format {
+            WavSampleFormat::Float32 => writer.write_sample(s),
+            WavSampleFormat::Pcm16 => writer.write_sample((s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16),
+            WavSampleFormat::Pcm24 => writer.write_sample((s.clamp(-1.0, 1.0) * 8_388_607.0) as i32),
+        };
+        result.map_err(|e| format!("Failed to write WAV sample: {}", e))?;
+    }
+
+    writer.finalize().map_err(|e| format!("Failed to finalize WAV: {}", e))?;
+    Ok(())
+}
+
+/// Read any WAV file — ours or an externally recorded one — back out as
+/// interleaved float32 samples plus its sample rate and channel count, so
+/// callers can validate/resample against what they actually need rather
+/// than assuming the file matches our own capture format.
+pub fn read_wav(path: &str) -> Result<(Vec<f32>, u32, u16), String> {
+    let mut reader = hound::WavReader::open(path).map_err(|e| format!("Failed to open WAV {}: {}", path, e))?;
+    let spec = reader.spec();
+
+    let samples: Result<Vec<f32>, String> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .map(|s| s.map_err(|e| format!("Failed to read WAV sample: {}", e)))
+            .collect(),
+        hound::SampleFormat::Int => {
+            let max = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|v| v as f32 / max).map_err(|e| format!("Failed to read WAV sample: {}", e)))
+                .collect()
+        }
+    };
+
+    Ok((samples?, spec.sample_rate, spec.channels))
+}
+
+/// Sum two interleaved float tracks sample-for-sample and renormalize so the
+/// mix doesn't clip just because both sources were already near full scale.
+/// Tracks of different lengths are mixed up to the shorter one's length,
+/// with the longer track's remainder appended unchanged — better to keep a
+/// few extra seconds of one source than to truncate silently.
+pub fn mix_tracks(a: &[f32], b: &[f32]) -> Vec<f32> {
+    let common = a.len().min(b.len());
+    let mut mixed = Vec::with_capacity(a.len().max(b.len()));
+
+    for i in 0..common {
+        mixed.push((a[i] + b[i]) * 0.5);
+    }
+    if a.len() > common {
+        mixed.extend_from_slice(&a[common..]);
+    } else if b.len() > common {
+        mixed.extend_from_slice(&b[common..]);
+    }
+
+    mixed
+}
@@ -0,0 +1,158 @@
+//! Near-real-time captioning during capture. `AudioRecorder` (in
+//! `mac::capture`) pushes every sample buffer it receives from
+//! ScreenCaptureKit in here as it arrives; a background worker slices that
+//! into overlapping ~20s windows, transcribes each with
+//! [`transcription::whisper::transcribe_window`], and emits the resulting
+//! segments to the frontend as they're ready instead of making it wait for
+//! `stop_capture`.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use super::transcription::whisper;
+
+/// How much audio each window covers, and how much of the previous window
+/// it repeats so words don't get clipped at the boundary.
+const WINDOW_SECS: f64 = 20.0;
+const OVERLAP_SECS: f64 = 3.0;
+/// How often the worker checks whether a full window is available yet.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Live accumulator for one capture session's audio, and the worker thread
+/// that drains it. Samples are mono at `sample_rate`; `AudioRecorder`
+/// downmixes ScreenCaptureKit's stereo buffers before calling `push`.
+pub struct StreamingTranscriber {
+    app_handle: AppHandle,
+    buffer: Mutex<VecDeque<f32>>,
+    sample_rate: u32,
+    /// Index (in samples, at `sample_rate`) of whatever is currently at the
+    /// front of `buffer` — i.e. how much audio has already been consumed by
+    /// a prior window. Used to compute each window's absolute start time.
+    consumed_samples: AtomicU64,
+    /// End time (seconds) of the last segment emitted, so the next window's
+    /// overlap region doesn't re-emit words already sent to the frontend.
+    last_emitted_end: Mutex<f64>,
+    stop: Arc<AtomicBool>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl StreamingTranscriber {
+    /// Start a new session's worker thread. Resets cached language
+    /// detection so a new recording isn't biased by whatever the previous
+    /// one was transcribed as.
+    pub fn start(app_handle: AppHandle, sample_rate: u32) -> Arc<Self> {
+        whisper::reset_window_language();
+
+        let this = Arc::new(Self {
+            app_handle,
+            buffer: Mutex::new(VecDeque::new()),
+            sample_rate,
+            consumed_samples: AtomicU64::new(0),
+            last_emitted_end: Mutex::new(0.0),
+            stop: Arc::new(AtomicBool::new(false)),
+            worker: Mutex::new(None),
+        });
+
+        let worker_ref = this.clone();
+        let handle = thread::spawn(move || worker_ref.run());
+        if let Ok(mut guard) = this.worker.lock() {
+            *guard = Some(handle);
+        }
+
+        this
+    }
+
+    /// Append newly captured mono samples to the ring buffer.
+    pub fn push(&self, samples: &[f32]) {
+        if let Ok(mut buf) = self.buffer.lock() {
+            buf.extend(samples);
+        }
+    }
+
+    /// Signal the worker to transcribe whatever's left (even if it's
+    /// shorter than a full window) and exit, then join it.
+    pub fn stop(self: Arc<Self>) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Ok(mut guard) = self.worker.lock() {
+            if let Some(handle) = guard.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    fn window_samples(&self) -> usize {
+        (WINDOW_SECS * self.sample_rate as f64) as usize
+    }
+
+    fn overlap_samples(&self) -> usize {
+        (OVERLAP_SECS * self.sample_rate as f64) as usize
+    }
+
+    fn run(&self) {
+        loop {
+            let stopping = self.stop.load(Ordering::SeqCst);
+            let ready = self.buffer.lock().map(|b| b.len()).unwrap_or(0);
+
+            if ready >= self.window_samples() {
+                self.transcribe_next_window(self.window_samples(), false);
+            } else if stopping {
+                if ready > 0 {
+                    self.transcribe_next_window(ready, true);
+                }
+                break;
+            } else {
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+
+    /// Drain `take` samples from the front of the buffer, transcribe them,
+    /// and emit any segments that fall after what's already been sent. Every
+    /// window but the last retains an overlap region for the next window;
+    /// the final flush (`is_final`) consumes everything since there won't
+    /// be a next window to carry it into.
+    fn transcribe_next_window(&self, take: usize, is_final: bool) {
+        let overlap = if is_final { 0 } else { self.overlap_samples().min(take.saturating_sub(1)) };
+        let advance = take - overlap;
+
+        let window: Vec<f32> = {
+            let buf = match self.buffer.lock() {
+                Ok(b) => b,
+                Err(_) => return,
+            };
+            buf.iter().take(take).copied().collect()
+        };
+
+        let window_start_secs = self.consumed_samples.load(Ordering::SeqCst) as f64 / self.sample_rate as f64;
+
+        match whisper::transcribe_window(&self.app_handle, &window, self.sample_rate, window_start_secs) {
+            Ok(segments) => {
+                let mut last_end = match self.last_emitted_end.lock() {
+                    Ok(guard) => guard,
+                    Err(_) => return,
+                };
+                for segment in segments {
+                    if segment.end <= *last_end {
+                        continue; // already covered by the previous window's overlap
+                    }
+                    *last_end = segment.end;
+                    let _ = self.app_handle.emit("streaming-transcription-segment", &segment);
+                }
+            }
+            Err(e) => {
+                eprintln!("[Logia ERROR] Streaming transcription window failed: {}", e);
+            }
+        }
+
+        if let Ok(mut buf) = self.buffer.lock() {
+            for _ in 0..advance.min(buf.len()) {
+                buf.pop_front();
+            }
+        }
+        self.consumed_samples.fetch_add(advance as u64, Ordering::SeqCst);
+    }
+}
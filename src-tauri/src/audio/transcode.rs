@@ -0,0 +1,191 @@
+//! Optional post-capture transcode stage. Each `os_capture::stop_capture`
+//! validates its WAV and then calls [`transcode_if_requested`] with the
+//! format chosen at `start_capture`; the `stop_recording` command then
+//! applies the config-driven [`TranscodeSettings`] via
+//! [`transcode_captured_audio`] to whatever is still a plain WAV. A
+//! transcode failure just means the original WAV is kept — it's never
+//! treated as a capture failure.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tauri::AppHandle;
+
+use crate::get_config_directory;
+
+/// Compressed container to transcode into. `Opus`/`Vorbis` pack into Ogg
+/// (small, good for speech at low bitrate); `Aac` packs into M4A (wider
+/// native playback support, e.g. on Windows/macOS); `Flac` is lossless,
+/// trading most of the size savings for exact preservation of the captured
+/// dynamic range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioCodec {
+    Opus,
+    Aac,
+    Flac,
+    Vorbis,
+}
+
+impl AudioCodec {
+    fn extension(&self) -> &'static str {
+        match self {
+            AudioCodec::Opus => "ogg",
+            AudioCodec::Aac => "m4a",
+            AudioCodec::Flac => "flac",
+            AudioCodec::Vorbis => "ogg",
+        }
+    }
+
+    fn ffmpeg_codec_name(&self) -> &'static str {
+        match self {
+            AudioCodec::Opus => "libopus",
+            AudioCodec::Aac => "aac",
+            AudioCodec::Flac => "flac",
+            AudioCodec::Vorbis => "libvorbis",
+        }
+    }
+
+    /// FLAC is lossless and encoded by compression effort, not bitrate;
+    /// every other codec here is lossy and encoded to a target bitrate.
+    fn is_lossless(&self) -> bool {
+        matches!(self, AudioCodec::Flac)
+    }
+}
+
+/// User-selectable transcode options, read from `config.json`'s
+/// `audio_transcode` object. Off by default — capture keeps producing plain
+/// WAVs until a user opts in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscodeSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_codec")]
+    pub codec: AudioCodec,
+    #[serde(default = "default_bitrate_kbps")]
+    pub bitrate_kbps: u32,
+}
+
+fn default_codec() -> AudioCodec {
+    AudioCodec::Opus
+}
+
+fn default_bitrate_kbps() -> u32 {
+    32
+}
+
+impl Default for TranscodeSettings {
+    fn default() -> Self {
+        Self { enabled: false, codec: default_codec(), bitrate_kbps: default_bitrate_kbps() }
+    }
+}
+
+/// Sidecar metadata written next to the final (possibly transcoded) audio
+/// file so any device that later syncs it down knows which codec to decode
+/// with, since the file extension alone stops being a safe signal once a
+/// `.wav`-shaped capture can come out the other end as Opus or AAC.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AudioFileMeta {
+    codec: String,
+    bitrate_kbps: Option<u32>,
+}
+
+pub fn load_transcode_settings(app_handle: &AppHandle) -> TranscodeSettings {
+    let Ok(config_dir) = get_config_directory(app_handle) else { return TranscodeSettings::default() };
+    let config_file = config_dir.join("config.json");
+    let Ok(content) = fs::read_to_string(&config_file) else { return TranscodeSettings::default() };
+    let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) else { return TranscodeSettings::default() };
+
+    config
+        .get("audio_transcode")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+fn meta_path(audio_path: &Path) -> std::path::PathBuf {
+    audio_path.with_extension(format!("{}.meta.json", audio_path.extension().and_then(|e| e.to_str()).unwrap_or("")))
+}
+
+fn write_meta(audio_path: &Path, codec: &str, bitrate_kbps: Option<u32>) {
+    let meta = AudioFileMeta { codec: codec.to_string(), bitrate_kbps };
+    if let Ok(content) = serde_json::to_string_pretty(&meta) {
+        let _ = fs::write(meta_path(audio_path), content);
+    }
+}
+
+/// Encode `wav_path` into `format` at the default bitrate if one was chosen
+/// at `start_capture`, otherwise hand the WAV path back unchanged. Each
+/// `os_capture` backend's `stop_capture` calls this as its last step so a
+/// format picked at recording start doesn't need its own per-platform
+/// encoder invocation — they all converge on a plain WAV first and share
+/// this one compression step.
+pub fn transcode_if_requested(wav_path: &str, format: Option<AudioCodec>) -> String {
+    match format {
+        Some(codec) => transcode_captured_audio(wav_path, &TranscodeSettings { enabled: true, codec, bitrate_kbps: default_bitrate_kbps() }),
+        None => wav_path.to_string(),
+    }
+}
+
+/// Transcode `wav_path` per `settings`, returning the path callers should
+/// use going forward (the original WAV path if disabled or on failure, the
+/// new compressed file's path on success). Encodes to a temp file first and
+/// only swaps it in with an atomic rename once ffmpeg has fully succeeded,
+/// so a crash or failed encode never leaves the caller without a usable
+/// recording.
+pub fn transcode_captured_audio(wav_path: &str, settings: &TranscodeSettings) -> String {
+    if !settings.enabled {
+        return wav_path.to_string();
+    }
+
+    let wav_path_buf = Path::new(wav_path);
+    let ext = settings.codec.extension();
+    let final_path = wav_path_buf.with_extension(ext);
+    let temp_path = wav_path_buf.with_extension(format!("{}.tmp", ext));
+
+    let mut args = vec!["-y".to_string(), "-i".to_string(), wav_path.to_string(), "-c:a".to_string(), settings.codec.ffmpeg_codec_name().to_string()];
+    if settings.codec.is_lossless() {
+        args.push("-compression_level".to_string());
+        args.push("5".to_string());
+    } else {
+        args.push("-b:a".to_string());
+        args.push(format!("{}k", settings.bitrate_kbps));
+    }
+    // The temp file's `.tmp` suffix hides the container from ffmpeg's
+    // extension-based guess, so name the muxer explicitly.
+    let muxer = match settings.codec {
+        AudioCodec::Opus | AudioCodec::Vorbis => "ogg",
+        AudioCodec::Aac => "ipod",
+        AudioCodec::Flac => "flac",
+    };
+    args.push("-f".to_string());
+    args.push(muxer.to_string());
+    args.push(temp_path.to_string_lossy().to_string());
+
+    let status = Command::new("ffmpeg").args(&args).output();
+
+    match status {
+        Ok(output) if output.status.success() => {
+            if let Err(e) = fs::rename(&temp_path, &final_path) {
+                log::warn!("Transcode succeeded but couldn't replace original file: {}", e);
+                let _ = fs::remove_file(&temp_path);
+                return wav_path.to_string();
+            }
+            let _ = fs::remove_file(wav_path_buf);
+            let codec_name = format!("{:?}", settings.codec).to_lowercase();
+            write_meta(&final_path, &codec_name, Some(settings.bitrate_kbps));
+            final_path.to_string_lossy().to_string()
+        }
+        Ok(output) => {
+            log::warn!("ffmpeg transcode failed, keeping original WAV: {}", String::from_utf8_lossy(&output.stderr));
+            let _ = fs::remove_file(&temp_path);
+            write_meta(wav_path_buf, "wav", None);
+            wav_path.to_string()
+        }
+        Err(e) => {
+            log::warn!("Could not launch ffmpeg for transcode, keeping original WAV: {}", e);
+            write_meta(wav_path_buf, "wav", None);
+            wav_path.to_string()
+        }
+    }
+}
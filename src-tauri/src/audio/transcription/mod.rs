@@ -1,13 +1,36 @@
+pub mod whisper;
+
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "python-transcription")]
 use std::path::PathBuf;
+#[cfg(feature = "python-transcription")]
 use std::process::Command;
+#[cfg(feature = "python-transcription")]
 use tauri::{path::BaseDirectory, AppHandle, Manager};
 
+/// One decoded word within a [`TranscriptionSegment`], timed via the
+/// decoder's own `<|timestamp|>` tokens rather than true cross-attention
+/// alignment — see [`whisper::decode_chunk`] for how `start`/`end` are
+/// derived. Good enough for karaoke-style highlighting and click-to-seek;
+/// not frame-accurate the way a forced aligner would be.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WordTimestamp {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+    pub confidence: f64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TranscriptionSegment {
     pub text: String,
     pub start: f64,
     pub end: f64,
+    pub words: Vec<WordTimestamp>,
+    /// Speaker label for this segment, e.g. `"speaker_0"`. `None` until
+    /// diarization exists upstream; present now so the frontend can group
+    /// contiguous segments by speaker turn as soon as it's populated.
+    pub speaker: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,6 +47,7 @@ pub struct TranscriptionError {
 }
 
 // Helper to find the python executable inside a venv across platforms
+#[cfg(feature = "python-transcription")]
 fn python_executable_in_venv(venv_path: &PathBuf) -> PathBuf {
     if let Ok(path) = std::env::var("LOGIA_PYTHON_PATH") {
         return PathBuf::from(path);
@@ -48,6 +72,12 @@ fn python_executable_in_venv(venv_path: &PathBuf) -> PathBuf {
     }
 }
 
+/// Python-subprocess fallback, kept for environments where the native
+/// candle-transformers path (see [`whisper::transcribe_native`]) isn't
+/// viable — e.g. a model checkpoint hasn't been downloaded yet, or the
+/// platform's candle backend has a known issue. Only compiled in when the
+/// `python-transcription` feature is enabled.
+#[cfg(feature = "python-transcription")]
 pub fn transcribe(app_handle: &AppHandle, wav_path: &str) -> Result<String, String> {
     let app_data_dir = app_handle.path().app_data_dir().unwrap();
     let venv_path = app_data_dir.join("transcription_venv");
@@ -0,0 +1,456 @@
+//! In-process Whisper transcription built on candle-transformers, so a
+//! working install no longer depends on the `transcribe.py` / venv dance in
+//! the parent module. Model weights, config and tokenizer are read from
+//! `<app-data>/whisper-model`; this module does not download them (a
+//! missing file is reported as an error) and only does inference.
+//!
+//! Pipeline: resample the captured WAV to 16 kHz mono, compute an 80-bin
+//! log-mel spectrogram in 30-second windows (25ms window / 10ms hop, padded
+//! or truncated to exactly 30s per the model's fixed input size), run the
+//! encoder once per window and greedily decode tokens (including
+//! `<|timestamp|>` tokens, which double as segment boundaries) from the
+//! decoder. Language is detected once, from the first window's language-
+//! token logits, and reused for the rest of the file.
+
+use candle_core::{DType, Device, IndexOp, Tensor};
+use candle_nn::VarBuilder;
+use candle_transformers::models::whisper::{self as wm, Config};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::{path::BaseDirectory, AppHandle, Manager};
+use tokenizers::Tokenizer;
+
+use crate::audio::pcm;
+
+use super::{TranscriptionResult, TranscriptionSegment, WordTimestamp};
+
+/// Whisper operates on fixed 30-second windows regardless of the clip's
+/// actual length; shorter windows are zero-padded, longer audio is chunked.
+const CHUNK_SECS: f64 = 30.0;
+
+struct LoadedModel {
+    /// Guards the decoder's internal KV-cache, which `detect_language`/
+    /// `decode_chunk` mutate. `transcribe_native` (on Tokio's blocking pool)
+    /// and `StreamingTranscriber`'s dedicated worker thread (`transcribe_window`)
+    /// both reach this same cached model independently, so real locking —
+    /// not just a "calls don't overlap in practice" assumption — is required
+    /// to avoid two decodes racing on the same KV-cache.
+    model: std::sync::Mutex<wm::model::Whisper>,
+    config: Config,
+    tokenizer: Tokenizer,
+    mel_filters: Vec<f32>,
+    device: Device,
+}
+
+/// Model load is expensive (reading + deserializing weights); cache it for
+/// the process lifetime rather than reloading on every `transcribe_native`
+/// call, the same reasoning as the Python path precompiling its venv once.
+static MODEL: OnceLock<Result<LoadedModel, String>> = OnceLock::new();
+
+fn model_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?
+        .join("whisper-model");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create whisper-model dir: {}", e))?;
+    Ok(dir)
+}
+
+fn mel_filters_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    // Bundled as a resource (the standard 80-bin filterbank used by every
+    // Whisper checkpoint), same way `transcribe.py` is resolved elsewhere.
+    if cfg!(debug_assertions) {
+        Ok(PathBuf::from("src/audio/transcription/melfilters.bytes"))
+    } else {
+        app_handle
+            .path()
+            .resolve("src/audio/transcription/melfilters.bytes", BaseDirectory::Resource)
+            .map_err(|e| format!("Failed to resolve melfilters.bytes: {}", e))
+    }
+}
+
+fn load_model(app_handle: &AppHandle) -> Result<(), String> {
+    if MODEL.get().is_some() {
+        return Ok(());
+    }
+
+    let result = (|| -> Result<LoadedModel, String> {
+        let dir = model_dir(app_handle)?;
+        let weights_path = dir.join("model.safetensors");
+        let config_path = dir.join("config.json");
+        let tokenizer_path = dir.join("tokenizer.json");
+
+        for p in [&weights_path, &config_path, &tokenizer_path] {
+            if !p.exists() {
+                return Err(format!(
+                    "Whisper model file missing at {:?}; download the model into {:?} before transcribing",
+                    p, dir
+                ));
+            }
+        }
+
+        let config: Config = serde_json::from_str(
+            &std::fs::read_to_string(&config_path).map_err(|e| format!("Failed to read whisper config: {}", e))?,
+        )
+        .map_err(|e| format!("Failed to parse whisper config: {}", e))?;
+
+        let tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| format!("Failed to load whisper tokenizer: {}", e))?;
+
+        let device = Device::Cpu;
+        let vb = unsafe {
+            VarBuilder::from_mmaped_safetensors(&[weights_path], DType::F32, &device)
+                .map_err(|e| format!("Failed to map whisper weights: {}", e))?
+        };
+        let model = wm::model::Whisper::load(&vb, config.clone())
+            .map_err(|e| format!("Failed to construct whisper model: {}", e))?;
+
+        let mel_bytes = std::fs::read(mel_filters_path(app_handle)?)
+            .map_err(|e| format!("Failed to read mel filterbank: {}", e))?;
+        let mut mel_filters = vec![0f32; mel_bytes.len() / 4];
+        candle_core::utils::read_f32le_into(&mel_bytes, &mut mel_filters);
+
+        Ok(LoadedModel { model: std::sync::Mutex::new(model), config, tokenizer, mel_filters, device })
+    })();
+
+    let _ = MODEL.set(result);
+    Ok(())
+}
+
+/// Read `path` — one of our own captures, or a file the user pointed
+/// `transcribe_audio` at directly — via `pcm::read_wav`, downmix to mono
+/// and linearly resample to the model's required 16 kHz. Going through
+/// `hound` rather than a hand-rolled chunk walker means an externally
+/// recorded WAV (a different bit depth, `WAVE_FORMAT_EXTENSIBLE`, odd
+/// chunk ordering) is read just as reliably as our own captures.
+fn read_wav_mono_16k(path: &str) -> Result<Vec<f32>, String> {
+    let (samples, sample_rate, channels) = pcm::read_wav(path)?;
+
+    let mono: Vec<f32> = if channels <= 1 {
+        samples
+    } else {
+        samples
+            .chunks(channels as usize)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect()
+    };
+
+    Ok(resample_mono_to_16k(&mono, sample_rate))
+}
+
+/// Linearly resample mono `samples` from `sample_rate` to Whisper's required
+/// 16 kHz. Shared by the file-based path above and the live streaming path
+/// below, which both end up needing the same conversion from whatever rate
+/// the capture backend produced.
+fn resample_mono_to_16k(samples: &[f32], sample_rate: u32) -> Vec<f32> {
+    if sample_rate == wm::audio::SAMPLE_RATE as u32 {
+        return samples.to_vec();
+    }
+
+    let ratio = wm::audio::SAMPLE_RATE as f64 / sample_rate as f64;
+    let out_len = ((samples.len() as f64) * ratio).round() as usize;
+    let mut resampled = Vec::with_capacity(out_len);
+    for i in 0..out_len {
+        let src_pos = i as f64 / ratio;
+        let idx = src_pos.floor() as usize;
+        let frac = (src_pos - idx as f64) as f32;
+        let a = samples.get(idx).copied().unwrap_or(0.0);
+        let b = samples.get(idx + 1).copied().unwrap_or(a);
+        resampled.push(a + (b - a) * frac);
+    }
+    resampled
+}
+
+/// Language tokens Whisper's multilingual tokenizer carries (`<|en|>`,
+/// `<|es|>`, ...); detection picks whichever the decoder's first step
+/// scores highest, mirroring the reference implementation's language-id
+/// step.
+fn detect_language(model: &mut wm::model::Whisper, tokenizer: &Tokenizer, mel: &Tensor) -> Result<(String, f64), String> {
+    let audio_features = model.encoder.forward(mel, true).map_err(|e| e.to_string())?;
+
+    let sot = token_id(tokenizer, wm::SOT_TOKEN)?;
+    let tokens = Tensor::new(&[sot], &audio_features.device()).map_err(|e| e.to_string())?.unsqueeze(0).map_err(|e| e.to_string())?;
+    let logits = model.decoder.forward(&tokens, &audio_features, true).map_err(|e| e.to_string())?;
+    let last = logits.i((0, 0)).map_err(|e| e.to_string())?;
+
+    let probs = candle_nn::ops::softmax(&last, 0).map_err(|e| e.to_string())?;
+    let probs_vec: Vec<f32> = probs.to_vec1().map_err(|e| e.to_string())?;
+
+    let mut best: Option<(String, f32)> = None;
+    for lang in wm::LANGUAGES.iter() {
+        let token = format!("<|{}|>", lang.0);
+        if let Some(id) = tokenizer.token_to_id(&token) {
+            let score = probs_vec.get(id as usize).copied().unwrap_or(0.0);
+            if best.as_ref().map(|(_, s)| score > *s).unwrap_or(true) {
+                best = Some((lang.0.to_string(), score));
+            }
+        }
+    }
+
+    drop(audio_features);
+    drop(logits);
+
+    let (lang, score) = best.ok_or("No language tokens found in tokenizer")?;
+    Ok((lang, score as f64))
+}
+
+fn token_id(tokenizer: &Tokenizer, token: &str) -> Result<u32, String> {
+    tokenizer.token_to_id(token).ok_or_else(|| format!("Token {} not found in tokenizer vocab", token))
+}
+
+/// Split a decoded segment's text into words, spreading `start..end` across
+/// them proportionally to word length. Greedy decoding with
+/// `<|timestamp|>` tokens only gives us segment-level boundaries, not true
+/// per-token cross-attention alignment, so this is a linear approximation
+/// rather than exact timing — close enough for karaoke-style highlighting,
+/// not for anything that needs frame accuracy. `confidence` is the
+/// segment's average token probability, applied uniformly since splitting
+/// it further per-word isn't meaningful at this granularity.
+fn words_from_segment(text: &str, start: f64, end: f64, confidence: f64) -> Vec<WordTimestamp> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    let total_chars: usize = words.iter().map(|w| w.len().max(1)).sum();
+    let duration = (end - start).max(0.0);
+
+    let mut cursor = start;
+    let mut out = Vec::with_capacity(words.len());
+    for word in words {
+        let share = word.len().max(1) as f64 / total_chars as f64;
+        let word_end = cursor + duration * share;
+        out.push(WordTimestamp {
+            word: word.to_string(),
+            start: cursor,
+            end: word_end,
+            confidence,
+        });
+        cursor = word_end;
+    }
+    out
+}
+
+/// Greedily decode one 30s window, returning the window's text plus
+/// segments split on `<|timestamp|>` tokens (offset by `chunk_start_secs`
+/// so they stay correct across the whole file, not just this window).
+fn decode_chunk(
+    model: &mut wm::model::Whisper,
+    tokenizer: &Tokenizer,
+    mel: &Tensor,
+    language: &str,
+    chunk_start_secs: f64,
+) -> Result<Vec<TranscriptionSegment>, String> {
+    let audio_features = model.encoder.forward(mel, true).map_err(|e| e.to_string())?;
+
+    let sot = token_id(tokenizer, wm::SOT_TOKEN)?;
+    let lang_token = token_id(tokenizer, &format!("<|{}|>", language))?;
+    let transcribe_token = token_id(tokenizer, wm::TRANSCRIBE_TOKEN)?;
+    let eot = token_id(tokenizer, wm::EOT_TOKEN)?;
+    let no_timestamps = tokenizer.token_to_id(wm::NO_TIMESTAMPS_TOKEN);
+
+    let mut tokens = vec![sot, lang_token, transcribe_token];
+    // Timestamps stay enabled (i.e. we don't push `no_timestamps`) since
+    // segment boundaries are read back from them below.
+    let _ = no_timestamps;
+
+    let mut segments = Vec::new();
+    let mut current_text_tokens: Vec<u32> = Vec::new();
+    let mut current_confidences: Vec<f64> = Vec::new();
+    let mut segment_start = chunk_start_secs;
+
+    for _ in 0..model.config.max_target_positions {
+        let input = Tensor::new(tokens.as_slice(), &audio_features.device()).map_err(|e| e.to_string())?.unsqueeze(0).map_err(|e| e.to_string())?;
+        let logits = model.decoder.forward(&input, &audio_features, tokens.len() <= 3).map_err(|e| e.to_string())?;
+        let last = logits.i((0, tokens.len() - 1)).map_err(|e| e.to_string())?;
+        let next_token = last.argmax(0).map_err(|e| e.to_string())?.to_scalar::<u32>().map_err(|e| e.to_string())?;
+        let probs = candle_nn::ops::softmax(&last, 0).map_err(|e| e.to_string())?;
+        let probs_vec: Vec<f32> = probs.to_vec1().map_err(|e| e.to_string())?;
+        let confidence = probs_vec.get(next_token as usize).copied().unwrap_or(0.0) as f64;
+        drop(logits);
+        drop(probs);
+
+        if next_token == eot {
+            break;
+        }
+
+        if wm::timestamp_token_id(next_token) {
+            let offset = chunk_start_secs + wm::timestamp_to_secs(next_token);
+            if !current_text_tokens.is_empty() {
+                let text = tokenizer.decode(&current_text_tokens, true).map_err(|e| e.to_string())?;
+                let avg_confidence = current_confidences.iter().sum::<f64>() / current_confidences.len() as f64;
+                let words = words_from_segment(&text, segment_start, offset, avg_confidence);
+                segments.push(TranscriptionSegment { text, start: segment_start, end: offset, words, speaker: None });
+                current_text_tokens.clear();
+                current_confidences.clear();
+            }
+            segment_start = offset;
+        } else {
+            current_text_tokens.push(next_token);
+            current_confidences.push(confidence);
+        }
+
+        tokens.push(next_token);
+    }
+
+    if !current_text_tokens.is_empty() {
+        let text = tokenizer.decode(&current_text_tokens, true).map_err(|e| e.to_string())?;
+        let end = chunk_start_secs + CHUNK_SECS;
+        let avg_confidence = current_confidences.iter().sum::<f64>() / current_confidences.len() as f64;
+        let words = words_from_segment(&text, segment_start, end, avg_confidence);
+        segments.push(TranscriptionSegment { text, start: segment_start, end, words, speaker: None });
+    }
+
+    drop(audio_features);
+    Ok(segments)
+}
+
+/// Transcribe `wav_path` entirely in-process. The model is loaded once and
+/// cached for the life of the app; subsequent calls only pay for reading
+/// the WAV and running inference.
+pub fn transcribe_native(app_handle: &AppHandle, wav_path: &str) -> Result<TranscriptionResult, String> {
+    load_model(app_handle)?;
+    let loaded = MODEL
+        .get()
+        .unwrap()
+        .as_ref()
+        .map_err(|e| e.clone())?;
+
+    let samples = read_wav_mono_16k(wav_path)?;
+    let total_secs = samples.len() as f64 / wm::audio::SAMPLE_RATE as f64;
+
+    let samples_per_chunk = (CHUNK_SECS * wm::audio::SAMPLE_RATE as f64) as usize;
+    let mut all_segments = Vec::new();
+    let mut detected_language: Option<(String, f64)> = None;
+
+    // `model`/`tokenizer` need `&mut` access for the decoder's internal KV
+    // cache; holding this lock for the whole call (rather than per-chunk)
+    // keeps one transcription's chunks sequential while still serializing
+    // against any other concurrent call — another `transcribe_audio`
+    // invocation or a live `StreamingTranscriber` session — instead of
+    // racing them on the same KV-cache.
+    let mut model_guard = loaded.model.lock().map_err(|e| format!("Mutex error: {}", e))?;
+
+    let mut chunk_start = 0usize;
+    let mut chunk_idx = 0usize;
+    while chunk_start < samples.len().max(1) {
+        let chunk_end = (chunk_start + samples_per_chunk).min(samples.len());
+        let mut chunk: Vec<f32> = samples.get(chunk_start..chunk_end).unwrap_or(&[]).to_vec();
+        chunk.resize(samples_per_chunk, 0.0); // pad the final, shorter window
+
+        let mel = wm::audio::pcm_to_mel(&loaded.config, &chunk, &loaded.mel_filters)
+            .map_err(|e: candle_core::Error| e.to_string())?;
+        let mel_len = mel.len();
+        let mel = Tensor::from_vec(mel, (1, loaded.config.num_mel_bins, mel_len / loaded.config.num_mel_bins), &loaded.device)
+            .map_err(|e| e.to_string())?;
+
+        if detected_language.is_none() {
+            detected_language = Some(detect_language(&mut model_guard, &loaded.tokenizer, &mel)?);
+        }
+        let (language, probability) = detected_language.clone().unwrap();
+
+        let chunk_start_secs = chunk_idx as f64 * CHUNK_SECS;
+        let mut segments = decode_chunk(&mut model_guard, &loaded.tokenizer, &mel, &language, chunk_start_secs)?;
+        all_segments.append(&mut segments);
+
+        // Drop the mel tensor explicitly before moving to the next chunk —
+        // candle's Metal/Candle allocator is known to hang onto freed
+        // tensors longer than CPU allocators would, and long recordings
+        // have enough chunks for that to add up to real memory pressure.
+        drop(mel);
+
+        chunk_idx += 1;
+        chunk_start = chunk_end;
+        if chunk_end >= samples.len() {
+            break;
+        }
+        let _ = probability;
+    }
+
+    let (language, language_probability) = detected_language.unwrap_or(("en".to_string(), 0.0));
+    let text = all_segments.iter().map(|s| s.text.trim()).collect::<Vec<_>>().join(" ");
+
+    eprintln!(
+        "[Logia DEBUG] Native whisper transcription of {} ({:.1}s) produced {} segments",
+        wav_path,
+        total_secs,
+        all_segments.len()
+    );
+
+    Ok(TranscriptionResult {
+        text,
+        language,
+        language_probability,
+        segments: all_segments,
+    })
+}
+
+#[allow(dead_code)]
+fn model_resource_hint(path: &Path) -> String {
+    format!("expected whisper model files under {:?}", path)
+}
+
+/// Language detected on a live session's first window, reused for every
+/// later window so mid-sentence pauses don't re-run (and potentially
+/// flip-flop) language detection. [`streaming`](super::streaming) resets
+/// this at the start of each capture via [`reset_window_language`].
+static WINDOW_LANGUAGE: OnceLock<std::sync::Mutex<Option<(String, f64)>>> = OnceLock::new();
+
+fn window_language_cache() -> &'static std::sync::Mutex<Option<(String, f64)>> {
+    WINDOW_LANGUAGE.get_or_init(|| std::sync::Mutex::new(None))
+}
+
+/// Clear the cached language so the next call to [`transcribe_window`]
+/// detects it fresh — call this when a new capture session starts.
+pub fn reset_window_language() {
+    if let Ok(mut guard) = window_language_cache().lock() {
+        *guard = None;
+    }
+}
+
+/// Transcribe one ~10-30s window of a live capture and return its segments,
+/// offset by `window_start_secs` so they stay correct against the session's
+/// total elapsed time. `samples` is mono audio at `sample_rate` (whatever
+/// rate the capture backend produced); resampling to Whisper's 16 kHz
+/// happens here. Used by the streaming pipeline instead of
+/// [`transcribe_native`], which is for a complete, already-stopped
+/// recording.
+pub fn transcribe_window(
+    app_handle: &AppHandle,
+    samples: &[f32],
+    sample_rate: u32,
+    window_start_secs: f64,
+) -> Result<Vec<TranscriptionSegment>, String> {
+    load_model(app_handle)?;
+    let loaded = MODEL.get().unwrap().as_ref().map_err(|e| e.clone())?;
+
+    let resampled = resample_mono_to_16k(samples, sample_rate);
+    let samples_per_chunk = (CHUNK_SECS * wm::audio::SAMPLE_RATE as f64) as usize;
+    let mut chunk = resampled;
+    chunk.resize(samples_per_chunk, 0.0);
+
+    let mel = wm::audio::pcm_to_mel(&loaded.config, &chunk, &loaded.mel_filters).map_err(|e: candle_core::Error| e.to_string())?;
+    let mel_len = mel.len();
+    let mel = Tensor::from_vec(mel, (1, loaded.config.num_mel_bins, mel_len / loaded.config.num_mel_bins), &loaded.device)
+        .map_err(|e| e.to_string())?;
+
+    // A live streaming session's worker thread and any concurrent
+    // `transcribe_native`/`transcribe_window` call reach this same cached
+    // model, so this blocks on the same lock `transcribe_native` takes
+    // rather than assuming the two never overlap.
+    let mut model_guard = loaded.model.lock().map_err(|e| format!("Mutex error: {}", e))?;
+
+    let language = {
+        let mut cache = window_language_cache().lock().map_err(|e| format!("Mutex error: {}", e))?;
+        if cache.is_none() {
+            *cache = Some(detect_language(&mut model_guard, &loaded.tokenizer, &mel)?);
+        }
+        cache.clone().unwrap().0
+    };
+
+    let segments = decode_chunk(&mut model_guard, &loaded.tokenizer, &mel, &language, window_start_secs);
+
+    drop(mel);
+    segments
+}
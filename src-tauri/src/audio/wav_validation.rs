@@ -0,0 +1,209 @@
+//! Sanity-checks a recording right after capture stops, so a crashed sidecar
+//! or a killed ffmpeg process can't hand a truncated or header-only WAV down
+//! to transcription/sync without anyone noticing. Results are cached by
+//! `(path, size, mtime)` in the app data dir, since `scan_local_files` may
+//! walk over the same audio files again later and there's no reason to
+//! re-parse a file that hasn't changed since it was last validated.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Seek};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, Manager};
+
+/// Why a captured recording failed validation.
+#[derive(Debug, thiserror::Error)]
+pub enum CaptureError {
+    #[error("Recording file not found: {0}")]
+    NotFound(String),
+
+    #[error("Not a WAV file (missing RIFF/WAVE header): {0}")]
+    NotAWavFile(String),
+
+    #[error("WAV file is missing its data chunk: {0}")]
+    MissingDataChunk(String),
+
+    #[error("Recording is truncated: data chunk claims {expected} bytes but only {actual} are present")]
+    TruncatedWav { expected: u32, actual: u64 },
+
+    #[error("Recording is empty (0 bytes of audio data)")]
+    EmptyRecording,
+
+    #[error("Recording is shorter than the minimum {minimum_secs:.1}s ({actual_secs:.2}s captured)")]
+    TooShort { actual_secs: f32, minimum_secs: f32 },
+
+    #[error("WAV chunk '{chunk_id}' declares a size of {declared} bytes but only {remaining} remain in the file")]
+    MalformedChunk { chunk_id: String, declared: u32, remaining: u64 },
+
+    #[error("I/O error reading recording: {0}")]
+    Io(String),
+}
+
+impl From<CaptureError> for String {
+    fn from(err: CaptureError) -> Self {
+        err.to_string()
+    }
+}
+
+/// Recordings shorter than this are treated as failed captures rather than
+/// legitimate (if brief) notes.
+const MIN_DURATION_SECS: f32 = 0.2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    size: u64,
+    mtime: u64,
+    valid: bool,
+}
+
+type ValidationCache = HashMap<String, CacheEntry>;
+
+fn cache_path(app_handle: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let audio_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("audio");
+    fs::create_dir_all(&audio_dir).map_err(|e| format!("Failed to create audio directory: {}", e))?;
+    Ok(audio_dir.join(".wav_validation_cache.json"))
+}
+
+fn load_cache(app_handle: &AppHandle) -> ValidationCache {
+    let Ok(path) = cache_path(app_handle) else { return ValidationCache::new() };
+    let Ok(content) = fs::read_to_string(&path) else { return ValidationCache::new() };
+    serde_json::from_str(&content).unwrap_or_default()
+}
+
+fn save_cache(app_handle: &AppHandle, cache: &ValidationCache) {
+    let Ok(path) = cache_path(app_handle) else { return };
+    if let Ok(content) = serde_json::to_string_pretty(cache) {
+        let _ = fs::write(&path, content);
+    }
+}
+
+fn file_fingerprint(metadata: &fs::Metadata) -> (u64, u64) {
+    let mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    (metadata.len(), mtime)
+}
+
+/// Validate a just-recorded WAV file, caching the outcome by `(path, size,
+/// mtime)`. A previously-validated file that hasn't changed size or mtime is
+/// trusted without being re-parsed. Only a cached *pass* is reused — a
+/// cached failure is not, since a subsequent save over the same path (e.g.
+/// after a retry) is expected to produce a new, possibly valid, file.
+pub fn validate_wav_file(app_handle: &AppHandle, path: &str) -> Result<(), CaptureError> {
+    let metadata = fs::metadata(path).map_err(|_| CaptureError::NotFound(path.to_string()))?;
+    let (size, mtime) = file_fingerprint(&metadata);
+
+    let mut cache = load_cache(app_handle);
+    if let Some(entry) = cache.get(path) {
+        if entry.size == size && entry.mtime == mtime && entry.valid {
+            return Ok(());
+        }
+    }
+
+    let result = check_wav_integrity(Path::new(path));
+
+    cache.insert(
+        path.to_string(),
+        CacheEntry { size, mtime, valid: result.is_ok() },
+    );
+    save_cache(app_handle, &cache);
+
+    result
+}
+
+/// Parse the RIFF/WAVE header, confirm the `data` chunk's declared length is
+/// actually present on disk, and require a minimum duration. The samples
+/// themselves are not inspected, so a silent recording of that length passes.
+fn check_wav_integrity(path: &Path) -> Result<(), CaptureError> {
+    let mut file = fs::File::open(path).map_err(|e| CaptureError::Io(e.to_string()))?;
+    let mut header = [0u8; 12];
+    file.read_exact(&mut header).map_err(|_| CaptureError::NotAWavFile(path.display().to_string()))?;
+
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        return Err(CaptureError::NotAWavFile(path.display().to_string()));
+    }
+
+    let file_len = fs::metadata(path).map_err(|e| CaptureError::Io(e.to_string()))?.len();
+
+    // Walk the chunk list looking for "fmt " (for byte rate) and "data".
+    let mut fmt_byte_rate: Option<u32> = None;
+    let mut data_declared_len: Option<u32> = None;
+    let mut data_offset: u64 = 0;
+
+    let mut offset: u64 = 12;
+    loop {
+        if offset + 8 > file_len {
+            break;
+        }
+        let mut chunk_header = [0u8; 8];
+        if file.read_exact(&mut chunk_header).is_err() {
+            break;
+        }
+        let chunk_id = &chunk_header[0..4];
+        let chunk_size = u32::from_le_bytes(chunk_header[4..8].try_into().unwrap());
+
+        if chunk_id == b"fmt " {
+            // `chunk_size` is an attacker/corruption-controlled u32 read straight off
+            // disk; clamp it against what's actually left in the file before trusting
+            // it as an allocation size, or a garbage value (e.g. 0xFFFFFFF0) turns a
+            // truncated/corrupted recording into a multi-GB allocation attempt instead
+            // of the clean validation error this function exists to produce.
+            let remaining = file_len.saturating_sub(offset + 8);
+            if chunk_size as u64 > remaining {
+                return Err(CaptureError::MalformedChunk {
+                    chunk_id: "fmt ".to_string(),
+                    declared: chunk_size,
+                    remaining,
+                });
+            }
+
+            let mut fmt_body = vec![0u8; chunk_size as usize];
+            if file.read_exact(&mut fmt_body).is_err() || fmt_body.len() < 16 {
+                break;
+            }
+            fmt_byte_rate = Some(u32::from_le_bytes(fmt_body[8..12].try_into().unwrap()));
+        } else if chunk_id == b"data" {
+            data_declared_len = Some(chunk_size);
+            data_offset = offset + 8;
+            break;
+        } else {
+            let _ = file.seek_relative(chunk_size as i64);
+        }
+
+        // Chunks are word-aligned; skip the pad byte if the size is odd.
+        offset += 8 + chunk_size as u64 + (chunk_size % 2) as u64;
+        if file.seek(std::io::SeekFrom::Start(offset)).is_err() {
+            break;
+        }
+    }
+
+    let data_declared_len = data_declared_len.ok_or_else(|| CaptureError::MissingDataChunk(path.display().to_string()))?;
+    let data_actual_len = file_len.saturating_sub(data_offset);
+
+    if data_actual_len < data_declared_len as u64 {
+        return Err(CaptureError::TruncatedWav { expected: data_declared_len, actual: data_actual_len });
+    }
+    if data_declared_len == 0 {
+        return Err(CaptureError::EmptyRecording);
+    }
+
+    if let Some(byte_rate) = fmt_byte_rate {
+        if byte_rate > 0 {
+            let duration_secs = data_declared_len as f32 / byte_rate as f32;
+            if duration_secs < MIN_DURATION_SECS {
+                return Err(CaptureError::TooShort { actual_secs: duration_secs, minimum_secs: MIN_DURATION_SECS });
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -8,8 +8,15 @@ use tauri::path::BaseDirectory;
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
+use crate::audio::{transcode::AudioCodec, CaptureMode};
+
 static CAPTURE_PROCESS: OnceLock<Mutex<Option<Child>>> = OnceLock::new();
 static OUTPUT_FILE_PATH: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+/// Compressed container chosen at `start_capture`, if any; see the mac
+/// backend's `OUTPUT_FORMAT` for the full rationale — the sidecar here
+/// still always writes a plain WAV, and `stop_capture` transcodes it
+/// afterward.
+static OUTPUT_FORMAT: OnceLock<Mutex<Option<AudioCodec>>> = OnceLock::new();
 
 fn generate_output_file(app_handle: &AppHandle) -> Result<String, String> {
     let timestamp = std::time::SystemTime::now()
@@ -32,7 +39,28 @@ fn generate_output_file(app_handle: &AppHandle) -> Result<String, String> {
 }
 
 pub fn start_capture(app_handle: &AppHandle) -> Result<(), String> {
-    println!("Starting audio capture on Windows");
+    start_capture_with_mode(app_handle, CaptureMode::SystemOnly, None, false)
+}
+
+/// `--mode` is passed straight through to the bundled `AudioCapture` sidecar
+/// as `system-only`/`mic-only`/`both`; mixing the mic track in, if
+/// requested, is the sidecar's job (it owns the actual WASAPI capture and
+/// file writing, unlike the macOS/Linux backends which do both in-process).
+/// `format`, unlike `mode`, isn't passed to the sidecar at all — it always
+/// writes a plain WAV, so `stop_capture` transcodes afterward instead.
+///
+/// `hls` is accepted for parity with the other backends but not honored:
+/// the sidecar is an opaque prebuilt binary with no segmenting support, so
+/// a request for HLS output here still just produces the usual monolithic
+/// WAV rather than silently pretending to segment it.
+pub fn start_capture_with_mode(app_handle: &AppHandle, mode: CaptureMode, format: Option<AudioCodec>, hls: bool) -> Result<(), String> {
+    if hls {
+        eprintln!("[Logia WARN] HLS segmenting was requested but isn't supported on Windows; recording a single WAV instead");
+    }
+    println!("Starting audio capture on Windows, mode={:?}, format={:?}", mode, format);
+
+    let format_mutex = OUTPUT_FORMAT.get_or_init(|| Mutex::new(None));
+    *format_mutex.lock().map_err(|e| format!("Mutex error: {e}"))? = format;
 
     if let Ok(app_data_dir) = app_handle.path().app_data_dir() {
         println!("App data dir: {:?}", app_data_dir);
@@ -53,10 +81,18 @@ pub fn start_capture(app_handle: &AppHandle) -> Result<(), String> {
 
     println!("Launching AudioCapture binary from: {:?}", sidecar_path);
 
+    let mode_arg = match mode {
+        CaptureMode::SystemOnly => "system-only",
+        CaptureMode::MicOnly => "mic-only",
+        CaptureMode::Both => "both",
+    };
+
     let mut cmd = Command::new(sidecar_path);
     cmd.args([
         "--output",
         &output_file,
+        "--mode",
+        mode_arg,
     ])
     .stdin(Stdio::piped())
     .stdout(Stdio::piped())
@@ -167,7 +203,7 @@ pub fn start_capture(app_handle: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
-pub fn stop_capture() -> Result<String, String> {
+pub fn stop_capture(app_handle: &AppHandle) -> Result<String, String> {
     let mut child = CAPTURE_PROCESS
         .get()
         .ok_or("Capture process not initialized")?
@@ -195,11 +231,16 @@ pub fn stop_capture() -> Result<String, String> {
         ));
     }
 
-    OUTPUT_FILE_PATH
+    let file_path = OUTPUT_FILE_PATH
         .get()
         .ok_or("Output path not initialized")?
         .lock()
         .map_err(|e| format!("Mutex error: {e}"))?
         .take()
-        .ok_or("Output path was not set".to_string())
+        .ok_or("Output path was not set".to_string())?;
+
+    crate::audio::wav_validation::validate_wav_file(app_handle, &file_path)?;
+
+    let format = OUTPUT_FORMAT.get().and_then(|m| m.lock().ok()).and_then(|mut g| g.take());
+    Ok(crate::audio::transcode::transcode_if_requested(&file_path, format))
 }
\ No newline at end of file
@@ -0,0 +1,390 @@
+//! Content-addressed, deduplicated backup/snapshot subsystem.
+//!
+//! Each note/folder/kanban record is split into variable-length chunks with a
+//! rolling buzhash, and each chunk is stored once under its SHA-256 digest in
+//! a shared chunk store. A snapshot is just a manifest listing, per record,
+//! the ordered chunk digests needed to reassemble it — so an incremental
+//! snapshot that only edited a few notes reuses every chunk it didn't touch.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use tauri::path::BaseDirectory;
+use uuid::Uuid;
+
+use crate::error::{KortexError, KortexResult};
+use crate::{Folder, KanbanTask, Note};
+
+const WINDOW_SIZE: usize = 48;
+/// Average chunk size is `2^MASK_BITS` bytes.
+const MASK_BITS: u32 = 13;
+const MIN_CHUNK_SIZE: usize = 2 * 1024;
+const MAX_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkRef {
+    pub digest: String,
+    pub len: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotRecord {
+    pub id: String,
+    pub chunks: Vec<ChunkRef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub id: String,
+    pub created_at: String,
+    pub notes: Vec<SnapshotRecord>,
+    pub folders: Vec<SnapshotRecord>,
+    pub kanban: Option<SnapshotRecord>,
+}
+
+fn backups_root(app_handle: &tauri::AppHandle) -> KortexResult<PathBuf> {
+    let dir = app_handle
+        .path()
+        .resolve("Kortex/backups", BaseDirectory::Document)
+        .map_err(|e| KortexError::PathResolution(format!("Could not resolve backups directory: {}", e)))?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn chunk_store_dir(app_handle: &tauri::AppHandle) -> KortexResult<PathBuf> {
+    let dir = backups_root(app_handle)?.join("chunks");
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn snapshots_dir(app_handle: &tauri::AppHandle) -> KortexResult<PathBuf> {
+    let dir = backups_root(app_handle)?.join("snapshots");
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// A fixed, app-wide table of per-byte values for the buzhash rolling window.
+/// Deterministic (not randomized) so chunk boundaries are reproducible across
+/// runs and machines.
+fn buzhash_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut state: u32 = 0x9e3779b9;
+    for (i, slot) in table.iter_mut().enumerate() {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state = state.wrapping_add(i as u32);
+        *slot = state;
+    }
+    table
+}
+
+/// Split `data` into content-defined chunks. Returns `(offset, len)` pairs
+/// covering the whole input.
+fn chunk_boundaries(data: &[u8]) -> Vec<(usize, usize)> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let table = buzhash_table();
+    let mask = (1u32 << MASK_BITS) - 1;
+    let rot = (WINDOW_SIZE % 32) as u32;
+
+    let mut boundaries = Vec::new();
+    let mut start = 0usize;
+    let mut hash: u32 = 0;
+
+    for i in 0..data.len() {
+        let incoming = table[data[i] as usize];
+        hash = hash.rotate_left(1) ^ incoming;
+
+        let window_len = i - start + 1;
+        if window_len > WINDOW_SIZE {
+            let outgoing = table[data[i - WINDOW_SIZE] as usize];
+            hash ^= outgoing.rotate_left(rot);
+        }
+
+        let chunk_len = i - start + 1;
+        let at_boundary = chunk_len >= MIN_CHUNK_SIZE && (hash & mask) == 0;
+        let forced = chunk_len >= MAX_CHUNK_SIZE;
+
+        if at_boundary || forced || i == data.len() - 1 {
+            boundaries.push((start, chunk_len));
+            start = i + 1;
+            hash = 0;
+        }
+    }
+
+    boundaries
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+fn chunk_path(store_dir: &PathBuf, digest: &str) -> PathBuf {
+    store_dir.join(&digest[0..2]).join(digest)
+}
+
+/// Write a single chunk into the store if it isn't already present.
+fn store_chunk(store_dir: &PathBuf, data: &[u8]) -> KortexResult<ChunkRef> {
+    let digest = sha256_hex(data);
+    let path = chunk_path(store_dir, &digest);
+
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, data)?;
+    }
+
+    Ok(ChunkRef { digest, len: data.len() })
+}
+
+/// Chunk `data` with the rolling hash and store each chunk, reusing any that
+/// already exist (from a prior snapshot, or shared across records).
+fn chunk_and_store(store_dir: &PathBuf, data: &[u8]) -> KortexResult<Vec<ChunkRef>> {
+    chunk_boundaries(data)
+        .into_iter()
+        .map(|(offset, len)| store_chunk(store_dir, &data[offset..offset + len]))
+        .collect()
+}
+
+/// Reassemble a record's bytes from its chunk list, verifying each digest.
+fn reassemble(store_dir: &PathBuf, chunks: &[ChunkRef]) -> KortexResult<Vec<u8>> {
+    let mut out = Vec::new();
+    for chunk_ref in chunks {
+        let path = chunk_path(store_dir, &chunk_ref.digest);
+        let data = fs::read(&path).map_err(|e| {
+            KortexError::Other(format!("Missing chunk {} in store: {}", chunk_ref.digest, e))
+        })?;
+
+        if sha256_hex(&data) != chunk_ref.digest {
+            return Err(KortexError::Other(format!(
+                "Chunk {} failed digest verification",
+                chunk_ref.digest
+            )));
+        }
+
+        out.extend_from_slice(&data);
+    }
+    Ok(out)
+}
+
+fn read_records<T: serde::de::DeserializeOwned>(dir: &PathBuf) -> Vec<(String, T)> {
+    let mut records = Vec::new();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                if let Ok(content) = fs::read_to_string(&path) {
+                    if let Ok(value) = serde_json::from_str::<T>(&content) {
+                        let file_stem = path
+                            .file_stem()
+                            .map(|s| s.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        records.push((file_stem, value));
+                    }
+                }
+            }
+        }
+    }
+    records
+}
+
+#[tauri::command]
+pub async fn create_snapshot(app_handle: tauri::AppHandle) -> KortexResult<SnapshotManifest> {
+    let store_dir = chunk_store_dir(&app_handle)?;
+
+    let notes_dir = crate::get_notes_directory(&app_handle)?;
+    let folders_dir = crate::get_folders_directory(&app_handle)?;
+    let kanban_dir = crate::get_kanban_directory(&app_handle)?;
+
+    let mut notes = Vec::new();
+    for (id, note) in read_records::<Note>(&notes_dir) {
+        let bytes = serde_json::to_vec(&note)?;
+        let chunks = chunk_and_store(&store_dir, &bytes)?;
+        notes.push(SnapshotRecord { id, chunks });
+    }
+
+    let mut folders = Vec::new();
+    for (id, folder) in read_records::<Folder>(&folders_dir) {
+        let bytes = serde_json::to_vec(&folder)?;
+        let chunks = chunk_and_store(&store_dir, &bytes)?;
+        folders.push(SnapshotRecord { id, chunks });
+    }
+
+    let kanban_path = kanban_dir.join("data.json");
+    let kanban = if kanban_path.exists() {
+        let content = fs::read_to_string(&kanban_path)?;
+        let tasks: Vec<KanbanTask> = serde_json::from_str(&content)?;
+        let bytes = serde_json::to_vec(&tasks)?;
+        let chunks = chunk_and_store(&store_dir, &bytes)?;
+        Some(SnapshotRecord { id: "data".to_string(), chunks })
+    } else {
+        None
+    };
+
+    let manifest = SnapshotManifest {
+        id: Uuid::new_v4().to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        notes,
+        folders,
+        kanban,
+    };
+
+    let manifest_path = snapshots_dir(&app_handle)?.join(format!("{}.json", manifest.id));
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    Ok(manifest)
+}
+
+fn load_manifest(app_handle: &tauri::AppHandle, snapshot_id: &str) -> KortexResult<SnapshotManifest> {
+    let path = snapshots_dir(app_handle)?.join(format!("{}.json", snapshot_id));
+    let content = fs::read_to_string(&path)
+        .map_err(|e| KortexError::Other(format!("Snapshot {} not found: {}", snapshot_id, e)))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[tauri::command]
+pub async fn restore_snapshot(app_handle: tauri::AppHandle, snapshot_id: String) -> KortexResult<()> {
+    let store_dir = chunk_store_dir(&app_handle)?;
+    let manifest = load_manifest(&app_handle, &snapshot_id)?;
+
+    let notes_dir = crate::get_notes_directory(&app_handle)?;
+    for record in &manifest.notes {
+        let bytes = reassemble(&store_dir, &record.chunks)?;
+        fs::write(notes_dir.join(format!("{}.json", record.id)), bytes)?;
+    }
+
+    let folders_dir = crate::get_folders_directory(&app_handle)?;
+    for record in &manifest.folders {
+        let bytes = reassemble(&store_dir, &record.chunks)?;
+        fs::write(folders_dir.join(format!("{}.json", record.id)), bytes)?;
+    }
+
+    if let Some(record) = &manifest.kanban {
+        let kanban_dir = crate::get_kanban_directory(&app_handle)?;
+        let bytes = reassemble(&store_dir, &record.chunks)?;
+        fs::write(kanban_dir.join("data.json"), bytes)?;
+    }
+
+    Ok(())
+}
+
+/// Delete every chunk in the store that isn't referenced by any snapshot
+/// manifest. Returns the number of chunks removed.
+#[tauri::command]
+pub async fn prune_snapshots(app_handle: tauri::AppHandle) -> KortexResult<usize> {
+    let store_dir = chunk_store_dir(&app_handle)?;
+    let snapshots_dir = snapshots_dir(&app_handle)?;
+
+    let mut referenced: HashSet<String> = HashSet::new();
+    if let Ok(entries) = fs::read_dir(&snapshots_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                if let Ok(content) = fs::read_to_string(&path) {
+                    if let Ok(manifest) = serde_json::from_str::<SnapshotManifest>(&content) {
+                        for record in manifest.notes.iter().chain(manifest.folders.iter()) {
+                            referenced.extend(record.chunks.iter().map(|c| c.digest.clone()));
+                        }
+                        if let Some(record) = &manifest.kanban {
+                            referenced.extend(record.chunks.iter().map(|c| c.digest.clone()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut deleted = 0;
+    if let Ok(prefixes) = fs::read_dir(&store_dir) {
+        for prefix_entry in prefixes.flatten() {
+            let prefix_path = prefix_entry.path();
+            if !prefix_path.is_dir() {
+                continue;
+            }
+            if let Ok(files) = fs::read_dir(&prefix_path) {
+                for file_entry in files.flatten() {
+                    let digest = file_entry.file_name().to_string_lossy().to_string();
+                    if !referenced.contains(&digest) {
+                        if fs::remove_file(file_entry.path()).is_ok() {
+                            deleted += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_input_has_no_chunks() {
+        assert_eq!(chunk_boundaries(&[]), Vec::new());
+    }
+
+    #[test]
+    fn boundaries_cover_the_whole_input_with_no_gaps_or_overlap() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let boundaries = chunk_boundaries(&data);
+
+        assert!(!boundaries.is_empty());
+        let mut expected_start = 0;
+        for &(start, len) in &boundaries {
+            assert_eq!(start, expected_start);
+            assert!(len > 0);
+            expected_start += len;
+        }
+        assert_eq!(expected_start, data.len());
+    }
+
+    #[test]
+    fn respects_min_and_max_chunk_size_except_for_the_final_chunk() {
+        let data: Vec<u8> = (0..500_000u32).map(|i| (i % 97) as u8).collect();
+        let boundaries = chunk_boundaries(&data);
+        let last = boundaries.len() - 1;
+
+        for (idx, &(_, len)) in boundaries.iter().enumerate() {
+            assert!(len <= MAX_CHUNK_SIZE, "chunk {} exceeds MAX_CHUNK_SIZE: {}", idx, len);
+            if idx != last {
+                assert!(len >= MIN_CHUNK_SIZE, "chunk {} is below MIN_CHUNK_SIZE: {}", idx, len);
+            }
+        }
+    }
+
+    #[test]
+    fn identical_content_produces_identical_boundaries() {
+        let data: Vec<u8> = (0..100_000u32).map(|i| ((i * 7 + 3) % 256) as u8).collect();
+        assert_eq!(chunk_boundaries(&data), chunk_boundaries(&data.clone()));
+    }
+
+    #[test]
+    fn a_shared_prefix_yields_shared_leading_chunks() {
+        // Content-defined chunking's whole point: data appended after a shared
+        // prefix shouldn't perturb the boundaries already found within that prefix.
+        let prefix: Vec<u8> = (0..300_000u32).map(|i| ((i * 13 + 5) % 256) as u8).collect();
+        let mut extended = prefix.clone();
+        extended.extend((0..50_000u32).map(|i| ((i * 11) % 256) as u8));
+
+        let prefix_boundaries = chunk_boundaries(&prefix);
+        let extended_boundaries = chunk_boundaries(&extended);
+
+        let shared_count = prefix_boundaries.len() - 1; // last chunk of prefix may now extend further
+        assert_eq!(
+            &extended_boundaries[..shared_count],
+            &prefix_boundaries[..shared_count]
+        );
+    }
+}
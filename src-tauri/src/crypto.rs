@@ -0,0 +1,146 @@
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{AeadCore, Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use base64::{engine::general_purpose, Engine as _};
+use keyring::Entry;
+
+use crate::error::{KortexError, KortexResult};
+
+const KEYRING_SERVICE: &str = "Kortex";
+const MASTER_SECRET_USERNAME: &str = "master_secret";
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Version tag prefixed to every blob produced by the current scheme, so a
+/// future format change can still tell old ciphertexts apart.
+const CURRENT_VERSION: u8 = 1;
+
+/// Legacy format: a fixed app-wide key and the literal nonce `b"unique nonce"`,
+/// reused for every secret. Kept only so we can decrypt values written before
+/// this module existed; every encrypt now goes through [`encrypt`].
+fn legacy_key() -> &'static [u8; 32] {
+    b"kortex-app-encryption-key-32byte"
+}
+
+fn legacy_decrypt(encrypted: &str) -> KortexResult<String> {
+    let cipher_key = Key::<Aes256Gcm>::from_slice(legacy_key());
+    let cipher = Aes256Gcm::new(cipher_key);
+    let nonce = Nonce::from_slice(b"unique nonce");
+
+    let ciphertext = general_purpose::STANDARD
+        .decode(encrypted)
+        .map_err(|e| KortexError::Crypto(format!("Base64 decode failed: {}", e)))?;
+
+    let plaintext = cipher
+        .decrypt(nonce, ciphertext.as_ref())
+        .map_err(|e| KortexError::Crypto(format!("Legacy decryption failed: {}", e)))?;
+
+    String::from_utf8(plaintext).map_err(|e| KortexError::Crypto(format!("UTF-8 decode failed: {}", e)))
+}
+
+/// Get (or, on first run, create and persist) the machine/app-scoped secret
+/// that keys are derived from. Never embedded in the binary.
+fn get_or_create_master_secret() -> KortexResult<Vec<u8>> {
+    let entry = Entry::new(KEYRING_SERVICE, MASTER_SECRET_USERNAME)
+        .map_err(|e| KortexError::Keyring(format!("Failed to open keyring entry: {}", e)))?;
+
+    match entry.get_password() {
+        Ok(encoded) => {
+            return general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|e| KortexError::Crypto(format!("Failed to decode master secret: {}", e)));
+        }
+        Err(keyring::Error::NoEntry) => {
+            // No secret has been created yet — fall through and create one.
+        }
+        Err(e) => {
+            // Any other keyring error (locked session, timeout, a dismissed
+            // permission prompt) is transient or environmental, not "no
+            // secret exists yet". Treating it as the latter would generate
+            // and persist a brand-new secret over whatever already exists,
+            // silently orphaning every value encrypted under the old one.
+            return Err(KortexError::Keyring(format!("Failed to read keyring entry: {}", e)));
+        }
+    }
+
+    let mut secret = vec![0u8; 32];
+    aes_gcm::aead::rand_core::RngCore::fill_bytes(&mut OsRng, &mut secret);
+
+    entry
+        .set_password(&general_purpose::STANDARD.encode(&secret))
+        .map_err(|e| KortexError::Keyring(format!("Failed to persist master secret: {}", e)))?;
+
+    Ok(secret)
+}
+
+/// Derive a 256-bit AES key from the master secret and a per-value salt via
+/// Argon2id, so a leaked ciphertext doesn't also leak a directly-usable key.
+fn derive_key(secret: &[u8], salt: &[u8]) -> KortexResult<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(secret, salt, &mut key)
+        .map_err(|e| KortexError::Crypto(format!("Key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+/// Encrypt `plaintext` with a fresh random salt and nonce, returning a
+/// base64 blob laid out as `[version][salt][nonce][ciphertext]`.
+pub fn encrypt(plaintext: &str) -> KortexResult<String> {
+    let secret = get_or_create_master_secret()?;
+
+    let mut salt = [0u8; SALT_LEN];
+    aes_gcm::aead::rand_core::RngCore::fill_bytes(&mut OsRng, &mut salt);
+
+    let key_bytes = derive_key(&secret, &salt)?;
+    let key = Key::<Aes256Gcm>::from_slice(&key_bytes);
+    let cipher = Aes256Gcm::new(key);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|e| KortexError::Crypto(format!("Encryption failed: {}", e)))?;
+
+    let mut blob = Vec::with_capacity(1 + SALT_LEN + NONCE_LEN + ciphertext.len());
+    blob.push(CURRENT_VERSION);
+    blob.extend_from_slice(&salt);
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+
+    Ok(general_purpose::STANDARD.encode(blob))
+}
+
+/// Decrypt a blob produced by [`encrypt`], transparently falling back to the
+/// legacy fixed-key/fixed-nonce format for values encrypted before this
+/// module existed. Callers that persist the result (e.g. on next save)
+/// should re-encrypt through [`encrypt`] to migrate the stored blob forward.
+pub fn decrypt(encrypted: &str) -> KortexResult<String> {
+    let raw = general_purpose::STANDARD
+        .decode(encrypted)
+        .map_err(|e| KortexError::Crypto(format!("Base64 decode failed: {}", e)))?;
+
+    if raw.first() != Some(&CURRENT_VERSION) || raw.len() < 1 + SALT_LEN + NONCE_LEN {
+        return legacy_decrypt(encrypted);
+    }
+
+    let salt = &raw[1..1 + SALT_LEN];
+    let nonce_bytes = &raw[1 + SALT_LEN..1 + SALT_LEN + NONCE_LEN];
+    let ciphertext = &raw[1 + SALT_LEN + NONCE_LEN..];
+
+    let secret = get_or_create_master_secret()?;
+    let key_bytes = derive_key(&secret, salt)?;
+    let key = Key::<Aes256Gcm>::from_slice(&key_bytes);
+    let cipher = Aes256Gcm::new(key);
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    let plaintext = match cipher.decrypt(nonce, ciphertext) {
+        Ok(plaintext) => plaintext,
+        // A legacy blob can start with the same byte as `CURRENT_VERSION`.
+        Err(e) => {
+            return legacy_decrypt(encrypted)
+                .map_err(|_| KortexError::Crypto(format!("Decryption failed: {}", e)));
+        }
+    };
+
+    String::from_utf8(plaintext).map_err(|e| KortexError::Crypto(format!("UTF-8 decode failed: {}", e)))
+}
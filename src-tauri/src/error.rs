@@ -0,0 +1,75 @@
+use serde::{Serialize, Serializer};
+
+/// Central error type for all Tauri commands, so the frontend receives a
+/// tagged JSON object instead of an opaque string it can't branch on.
+#[derive(Debug, thiserror::Error)]
+pub enum KortexError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+
+    #[error("Keyring error: {0}")]
+    Keyring(String),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+
+    #[error("API key not configured")]
+    NotConfigured,
+
+    #[error("Path resolution error: {0}")]
+    PathResolution(String),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<String> for KortexError {
+    fn from(msg: String) -> Self {
+        KortexError::Other(msg)
+    }
+}
+
+impl From<&str> for KortexError {
+    fn from(msg: &str) -> Self {
+        KortexError::Other(msg.to_string())
+    }
+}
+
+/// Serialize as a tagged `{ "kind": "...", "message": "..." }` object so the
+/// frontend can match on `kind` instead of parsing a string.
+impl Serialize for KortexError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let kind = match self {
+            KortexError::Io(_) => "io",
+            KortexError::Serde(_) => "serde",
+            KortexError::Keyring(_) => "keyring",
+            KortexError::Crypto(_) => "crypto",
+            KortexError::NotConfigured => "not_configured",
+            KortexError::PathResolution(_) => "path_resolution",
+            KortexError::Other(_) => "other",
+        };
+
+        let mut state = serializer.serialize_struct("KortexError", 2)?;
+        state.serialize_field("kind", kind)?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+/// Lets call sites that haven't migrated off `Result<_, String>` yet keep
+/// using `?` against functions that now return `KortexError`.
+impl From<KortexError> for String {
+    fn from(err: KortexError) -> Self {
+        err.to_string()
+    }
+}
+
+pub type KortexResult<T> = Result<T, KortexError>;
@@ -10,11 +10,22 @@ use tauri::path::BaseDirectory;
 use tauri::Manager;
 use uuid::Uuid;
 use keyring::Entry;
-use aes_gcm::{Aes256Gcm, Key, Nonce};
-use aes_gcm::aead::{Aead, KeyInit};
-use base64::{Engine as _, engine::general_purpose};
 
 mod audio;
+mod backup;
+mod crypto;
+mod error;
+mod google_drive;
+mod logging;
+mod merge;
+mod python_discovery;
+mod retry_queue;
+mod sync_job;
+mod sync_manifest;
+mod wheel_installer;
+mod wheelhouse;
+
+use error::{KortexError, KortexResult};
 
 // Hide console windows on Windows when spawning subprocesses
 #[cfg(windows)]
@@ -28,37 +39,6 @@ fn hide_console(cmd: &mut std::process::Command) {
     }
 }
 
-// Encryption key derived from app name (in production, this should be more secure)
-fn get_encryption_key() -> &'static [u8; 32] {
-    b"kortex-app-encryption-key-32byte"
-}
-
-fn encrypt_api_key(key: &str) -> Result<String, String> {
-    let cipher_key = Key::<Aes256Gcm>::from_slice(get_encryption_key());
-    let cipher = Aes256Gcm::new(cipher_key);
-    let nonce = Nonce::from_slice(b"unique nonce"); // In production, use random nonce
-
-    let ciphertext = cipher.encrypt(nonce, key.as_bytes())
-        .map_err(|e| format!("Encryption failed: {}", e))?;
-
-    Ok(general_purpose::STANDARD.encode(ciphertext))
-}
-
-fn decrypt_api_key(encrypted: &str) -> Result<String, String> {
-    let cipher_key = Key::<Aes256Gcm>::from_slice(get_encryption_key());
-    let cipher = Aes256Gcm::new(cipher_key);
-    let nonce = Nonce::from_slice(b"unique nonce");
-
-    let ciphertext = general_purpose::STANDARD.decode(encrypted)
-        .map_err(|e| format!("Base64 decode failed: {}", e))?;
-
-    let plaintext = cipher.decrypt(nonce, ciphertext.as_ref())
-        .map_err(|e| format!("Decryption failed: {}", e))?;
-
-    String::from_utf8(plaintext)
-        .map_err(|e| format!("UTF-8 decode failed: {}", e))
-}
-
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -129,7 +109,7 @@ fn get_folders_directory(app_handle: &tauri::AppHandle) -> Result<PathBuf, Strin
     Ok(documents_dir)
 }
 
-fn get_config_directory(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+pub(crate) fn get_config_directory(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
     let config_dir = app_handle
         .path()
         .resolve("Kortex", BaseDirectory::AppConfig)
@@ -169,7 +149,7 @@ async fn create_note(
     note_type: String,
     folder_id: Option<String>,
     app_handle: tauri::AppHandle,
-) -> Result<Note, String> {
+) -> KortexResult<Note> {
     let notes_dir = get_notes_directory(&app_handle)?;
     let note_id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
@@ -185,10 +165,9 @@ async fn create_note(
     };
 
     let file_path = notes_dir.join(format!("{}.json", note_id));
-    let note_json = serde_json::to_string_pretty(&note)
-        .map_err(|e| format!("Failed to serialize note: {}", e))?;
+    let note_json = serde_json::to_string_pretty(&note)?;
 
-    fs::write(&file_path, note_json).map_err(|e| format!("Failed to write note file: {}", e))?;
+    fs::write(&file_path, note_json)?;
 
     Ok(note)
 }
@@ -219,17 +198,16 @@ async fn get_all_notes(app_handle: tauri::AppHandle) -> Result<Vec<Note>, String
 }
 
 #[tauri::command]
-async fn save_note(note: Note, app_handle: tauri::AppHandle) -> Result<(), String> {
+async fn save_note(note: Note, app_handle: tauri::AppHandle) -> KortexResult<()> {
     let notes_dir = get_notes_directory(&app_handle)?;
     let file_path = notes_dir.join(format!("{}.json", note.id));
 
     let mut updated_note = note;
     updated_note.updated_at = chrono::Utc::now().to_rfc3339();
 
-    let note_json = serde_json::to_string_pretty(&updated_note)
-        .map_err(|e| format!("Failed to serialize note: {}", e))?;
+    let note_json = serde_json::to_string_pretty(&updated_note)?;
 
-    fs::write(&file_path, note_json).map_err(|e| format!("Failed to write note file: {}", e))?;
+    fs::write(&file_path, note_json)?;
 
     Ok(())
 }
@@ -381,7 +359,7 @@ const KEYRING_SERVICE: &str = "Kortex";
 const KEYRING_USERNAME: &str = "google_api_key";
 
 #[tauri::command]
-async fn get_google_api_key(app_handle: tauri::AppHandle) -> Result<String, String> {
+async fn get_google_api_key(app_handle: tauri::AppHandle) -> KortexResult<String> {
     // Try keyring first (works on Windows Credential Manager, macOS Keychain, Linux Secret Service)
     if let Some(pw) = try_get_keyring(KEYRING_SERVICE, KEYRING_USERNAME) {
         return Ok(pw);
@@ -400,7 +378,7 @@ async fn get_google_api_key(app_handle: tauri::AppHandle) -> Result<String, Stri
 
         // Check for encrypted key
         if let Some(encrypted_key) = config.get("encrypted_google_api_key").and_then(|v| v.as_str()) {
-            let key = decrypt_api_key(encrypted_key)?;
+            let key = crypto::decrypt(encrypted_key)?;
             // Try to migrate into keyring for future
             let _ = try_set_keyring(KEYRING_SERVICE, KEYRING_USERNAME, &key);
             return Ok(key);
@@ -415,7 +393,7 @@ async fn get_google_api_key(app_handle: tauri::AppHandle) -> Result<String, Stri
                 if let Some(obj) = updated_config.as_object_mut() {
                     obj.remove("google_api_key");
                     // also attempt to store encrypted form
-                    if let Ok(encrypted) = encrypt_api_key(plain_key) {
+                    if let Ok(encrypted) = crypto::encrypt(plain_key) {
                         obj.insert("encrypted_google_api_key".to_string(), serde_json::Value::String(encrypted));
                     }
                 }
@@ -427,7 +405,7 @@ async fn get_google_api_key(app_handle: tauri::AppHandle) -> Result<String, Stri
         }
     }
 
-    Err("API key not configured".to_string())
+    Err(KortexError::NotConfigured)
 }
 
 #[tauri::command]
@@ -435,7 +413,7 @@ async fn save_google_api_key(key: String, app_handle: tauri::AppHandle) -> Resul
     // First attempt to save to keyring (preferred)
     if try_set_keyring(KEYRING_SERVICE, KEYRING_USERNAME, &key) {
         // Also persist an encrypted copy to config.json as a fallback for dev/reload scenarios
-        let encrypted_key = encrypt_api_key(&key)?;
+        let encrypted_key = crypto::encrypt(&key)?;
         let config_dir = get_config_directory(&app_handle)?;
         let config_file = config_dir.join("config.json");
 
@@ -466,7 +444,7 @@ async fn save_google_api_key(key: String, app_handle: tauri::AppHandle) -> Resul
     }
 
     // Fallback to encrypted config.json
-    let encrypted_key = encrypt_api_key(&key)?;
+    let encrypted_key = crypto::encrypt(&key)?;
 
     let config_dir = get_config_directory(&app_handle)?;
     let config_file = config_dir.join("config.json");
@@ -528,13 +506,27 @@ async fn remove_google_api_key(app_handle: tauri::AppHandle) -> Result<(), Strin
 }
 
 #[tauri::command]
-async fn start_recording(app_handle: tauri::AppHandle) -> Result<(), String> {
-    audio::os_capture::start_capture(&app_handle)
+async fn start_recording(
+    app_handle: tauri::AppHandle,
+    mode: Option<audio::CaptureMode>,
+    format: Option<audio::transcode::AudioCodec>,
+    hls: Option<bool>,
+) -> Result<(), String> {
+    audio::os_capture::start_capture_with_mode(&app_handle, mode.unwrap_or_default(), format, hls.unwrap_or(false))
 }
 
+/// `stop_capture` already transcodes into whatever format was chosen at
+/// `start_recording`, so its result only still needs the config-driven
+/// `audio_transcode` settings applied when it's still a plain WAV (i.e. no
+/// format was requested at start).
 #[tauri::command]
-async fn stop_recording() -> Result<String, String> {
-    audio::os_capture::stop_capture()
+async fn stop_recording(app_handle: tauri::AppHandle) -> Result<String, String> {
+    let output_path = audio::os_capture::stop_capture(&app_handle)?;
+    if std::path::Path::new(&output_path).extension().and_then(|e| e.to_str()) != Some("wav") {
+        return Ok(output_path);
+    }
+    let settings = audio::transcode::load_transcode_settings(&app_handle);
+    Ok(audio::transcode::transcode_captured_audio(&output_path, &settings))
 }
 
 // Helper to find the python executable inside a venv across platforms
@@ -559,11 +551,142 @@ fn python_executable_in_venv(venv_path: &std::path::PathBuf) -> std::path::PathB
     }
 }
 
-async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+/// Locate a venv's `site-packages` directory across platforms. Unix venvs
+/// nest it under a `pythonX.Y` directory whose exact name we don't control,
+/// so we look for whichever `python*` subdirectory of `lib` has one.
+fn venv_site_packages(venv_path: &std::path::Path) -> Option<std::path::PathBuf> {
+    if cfg!(windows) {
+        let candidate = venv_path.join("Lib").join("site-packages");
+        candidate.exists().then_some(candidate)
+    } else {
+        std::fs::read_dir(venv_path.join("lib")).ok()?.flatten().find_map(|entry| {
+            let path = entry.path();
+            let is_python_dir = path.is_dir()
+                && path.file_name().and_then(|n| n.to_str()).map(|n| n.starts_with("python")).unwrap_or(false);
+            if !is_python_dir {
+                return None;
+            }
+            let site_packages = path.join("site-packages");
+            site_packages.exists().then_some(site_packages)
+        })
+    }
+}
+
+/// Marker file left behind once a venv has been precompiled, so repeat
+/// `prereflight_check`/diagnostics calls don't need to recompile anything.
+const PRECOMPILED_MARKER: &str = ".precompiled";
+
+fn venv_is_precompiled(venv_path: &std::path::Path) -> bool {
+    venv_path.join(PRECOMPILED_MARKER).exists()
+}
+
+/// Count `.pyc` files under `dir` (recursively), used only to report how much
+/// got precompiled — not a correctness check.
+fn count_pyc_files(dir: &std::path::Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else { return 0 };
+    entries.flatten().fold(0, |count, entry| {
+        let path = entry.path();
+        if path.is_dir() {
+            count + count_pyc_files(&path)
+        } else if path.extension().and_then(|s| s.to_str()) == Some("pyc") {
+            count + 1
+        } else {
+            count
+        }
+    })
+}
+
+/// Best-effort bytecode precompilation so the first `transcribe_audio` call
+/// doesn't pay CPython's import-time compile cost for faster-whisper and its
+/// dependencies. `compileall` already skips any module that fails to compile
+/// and keeps going, so a partial failure here is not fatal — we just log
+/// however many `.pyc` files ended up on disk and drop a marker file so
+/// `prereflight_check` can report the venv as precompiled.
+fn precompile_venv(python_path: &std::path::Path, venv_path: &std::path::Path) {
+    let Some(site_packages) = venv_site_packages(venv_path) else {
+        log::warn!("Could not locate site-packages to precompile; skipping");
+        return;
+    };
+
+    log::info!("Precompiling installed dependencies to bytecode...");
+    let mut cmd = Command::new(python_path);
+    // "-qq" keeps compileall quiet about individual errors while still
+    // reporting a nonzero exit if any module failed, which we don't treat as
+    // fatal — it's a best-effort warm-up, not part of the install contract.
+    cmd.args(["-m", "compileall", "-qq", &site_packages.to_string_lossy()]);
+    hide_console(&mut cmd);
+    let result = cmd.env_remove("PYTHONHOME").env_remove("PYTHONPATH").status();
+
+    let compiled = count_pyc_files(&site_packages);
+    match result {
+        Ok(status) if status.success() => log::info!("Precompiled {} modules to bytecode", compiled),
+        _ => log::warn!("Precompilation finished with some failures; {} modules compiled", compiled),
+    }
+
+    if let Err(e) = std::fs::write(venv_path.join(PRECOMPILED_MARKER), compiled.to_string()) {
+        log::warn!("Failed to write precompiled marker: {}", e);
+    }
+}
+
+/// Install every wheel bundled in `wheelhouse_dir` straight into the venv via
+/// [`wheel_installer::install_wheel`], without ever invoking pip or uv. Each
+/// wheel's own tags are used as its target interpreter's tags, so
+/// `install_wheel`'s tag check always passes here and every `.whl` in the
+/// directory is installed. `check_wheelhouse` only confirmed beforehand that
+/// each requirement has some matching wheel there.
+fn install_wheelhouse_natively(
+    wheelhouse_dir: &std::path::Path,
+    venv_path: &std::path::Path,
+    python_path: &std::path::Path,
+) -> KortexResult<usize> {
+    let site_packages = venv_site_packages(venv_path)
+        .ok_or_else(|| KortexError::Other("Could not locate venv site-packages".to_string()))?;
+    let scripts_dir = if cfg!(windows) { venv_path.join("Scripts") } else { venv_path.join("bin") };
+
+    let mut installed = 0;
+    for entry in std::fs::read_dir(wheelhouse_dir)
+        .map_err(|e| KortexError::Other(format!("Failed to read wheelhouse dir: {}", e)))?
+        .flatten()
+    {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("whl") {
+            continue;
+        }
+
+        let wheel = wheel_installer::WheelName::parse(&path)?;
+        let target = wheel_installer::TargetInterpreter {
+            python_tag: wheel.python_tag.clone(),
+            abi_tag: wheel.abi_tag.clone(),
+            platform_tag: wheel.platform_tag.clone(),
+            venv_path: venv_path.to_path_buf(),
+            site_packages: site_packages.clone(),
+            scripts_dir: scripts_dir.clone(),
+            python_executable: python_path.to_path_buf(),
+        };
+
+        wheel_installer::install_wheel(&path, &target)?;
+        wheel_installer::verify_record(&site_packages.join(wheel.dist_info_prefix()), &site_packages)?;
+        installed += 1;
+    }
+
+    Ok(installed)
+}
+
+/// Default CPython version requested from uv's managed-interpreter download
+/// when no suitable system Python is found. Callers can request an
+/// alternative build (e.g. the free-threaded `3.13t`, or a `pypy3.x`) instead.
+const DEFAULT_MANAGED_PYTHON_VERSION: &str = "3.11";
+
+async fn ensure_transcription_dependencies(
+    app_handle: &tauri::AppHandle,
+    python_version: Option<&str>,
+) -> KortexResult<std::path::PathBuf> {
     use std::process::Command;
     use std::path::PathBuf;
     use tauri::path::BaseDirectory;
 
+    let python_version = python_version.unwrap_or(DEFAULT_MANAGED_PYTHON_VERSION);
+
     let requirements_path = app_handle.path().resolve("src/audio/transcription/requirements.txt", BaseDirectory::Resource)
         .map_err(|e| format!("Failed to resolve requirements.txt resource: {}", e))?;
 
@@ -577,20 +700,10 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
             .map_err(|e| format!("Failed to create app data directory: {}", e))?;
     }
 
-    // Prepare an install log that the UI can read while installation is running
-    let log_path = app_data_dir.join("transcription_install.log");
-    // helper to append to the log file (best-effort)
-    fn append_to_log(path: &std::path::PathBuf, msg: &str) {
-        use std::io::Write;
-        if let Ok(mut f) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
-            let _ = f.write_all(msg.as_bytes());
-            let _ = f.write_all(b"\n");
-        }
-    }
-
-    append_to_log(&log_path, &format!("[{}] Starting dependency check/install", chrono::Utc::now().to_rfc3339()));
+    log::info!("Starting dependency check/install");
 
     let venv_path = app_data_dir.join("transcription_venv");
+    let managed_python_dir = app_data_dir.join("managed_python");
 
     let mut cmd_uv_check = Command::new("uv");
     cmd_uv_check.arg("--version");
@@ -599,7 +712,7 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
         .output()
         .map(|output| output.status.success())
         .unwrap_or(false);
-    append_to_log(&log_path, &format!("uv available: {}", uv_available));
+    log::info!("uv available: {}", uv_available);
 
     // Python version check and venv recreation logic
     if venv_path.exists() {
@@ -616,8 +729,7 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
                 let version_str_err = String::from_utf8_lossy(&output.stderr);
 
                 if version_str.contains("3.14") || version_str_err.contains("3.14") {
-                    println!("Detected Python 3.14 in venv, which is likely incompatible. Recreating venv with 3.12...");
-                    append_to_log(&log_path, "Detected incompatible Python in venv, removing venv to recreate");
+                    log::warn!("Detected Python 3.14 in venv, which is likely incompatible. Recreating venv with 3.12...");
                     let _ = std::fs::remove_dir_all(&venv_path);
                 }
             }
@@ -626,17 +738,36 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
 
     // Create virtual environment if it doesn't exist
     if !venv_path.exists() {
-        println!("Creating virtual environment...");
-        append_to_log(&log_path, "Creating virtual environment...");
+        log::info!("Creating virtual environment...");
 
         let venv_created = if uv_available {
+            // Download a self-contained CPython build via uv's own managed-interpreter
+            // support rather than depending on whatever (if anything) is on PATH. This
+            // is what makes installs reproducible across machines that don't have a
+            // system Python at all.
+            std::fs::create_dir_all(&managed_python_dir).ok();
+
+            let mut cmd_uv_py_install = Command::new("uv");
+            cmd_uv_py_install.args(&["python", "install", python_version]);
+            cmd_uv_py_install.env("UV_PYTHON_INSTALL_DIR", &managed_python_dir);
+            hide_console(&mut cmd_uv_py_install);
+            let managed_install_ok = cmd_uv_py_install.status().map(|s| s.success()).unwrap_or(false);
+            log::info!("uv managed Python {} install: {}", python_version, managed_install_ok);
+
             let mut cmd_uv_venv = Command::new("uv");
-            cmd_uv_venv.args(&["venv", &venv_path.to_string_lossy(), "--python", "3.12"]);
+            cmd_uv_venv.args(&[
+                "venv",
+                &venv_path.to_string_lossy(),
+                "--python",
+                python_version,
+                "--python-preference",
+                "only-managed",
+            ]);
+            cmd_uv_venv.env("UV_PYTHON_INSTALL_DIR", &managed_python_dir);
             hide_console(&mut cmd_uv_venv);
             let status = cmd_uv_venv.status().map(|s| s.success()).unwrap_or(false);
             if status {
-                println!("Created venv with uv (Python 3.12)");
-                append_to_log(&log_path, "Created venv with uv (Python 3.12)");
+                log::info!("Created venv with uv-managed Python {}", python_version);
             }
             status
         } else if cfg!(windows) {
@@ -648,8 +779,7 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
             let created_with_py = cmd_py.status().map(|s| s.success()).unwrap_or(false);
 
             if created_with_py {
-                println!("Created venv with py launcher");
-                append_to_log(&log_path, "Created venv with py launcher");
+                log::info!("Created venv with py launcher");
             }
 
             if !created_with_py {
@@ -659,8 +789,7 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
                 let created_with_python = cmd_python.status().map(|s| s.success()).unwrap_or(false);
 
                 if created_with_python {
-                    println!("Created venv with python.exe");
-                    append_to_log(&log_path, "Created venv with python.exe");
+                    log::info!("Created venv with python.exe");
                 }
 
                 created_with_python
@@ -675,15 +804,14 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
             let status = cmd_py3.status().map(|s| s.success()).unwrap_or(false);
 
             if status {
-                println!("Created venv with python3");
-                append_to_log(&log_path, "Created venv with python3");
+                log::info!("Created venv with python3");
             }
             status
         };
 
         if !venv_created {
-            append_to_log(&log_path, "Failed to create virtual environment");
-            return Err("Failed to create virtual environment".to_string());
+            log::error!("Failed to create virtual environment");
+            return Err(KortexError::Other("Failed to create virtual environment".to_string()));
         }
     }
 
@@ -691,14 +819,13 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
     let python_path = python_executable_in_venv(&venv_path);
 
     if !python_path.exists() {
-        append_to_log(&log_path, "Python executable not found in venv after creation");
-        return Err("Python executable not found in venv after creation".to_string());
+        log::error!("Python executable not found in venv after creation");
+        return Err(KortexError::Other("Python executable not found in venv after creation".to_string()));
     }
 
     // Ensure pip/setuptools/wheel/cython and imageio-ffmpeg are available to improve build success
     // (helps avoid building C extensions like 'av' from source when possible)
-    println!("Upgrading pip/setuptools/wheel and installing build helpers (cython, imageio-ffmpeg)...");
-    append_to_log(&log_path, "Upgrading pip/setuptools/wheel and installing build helpers (cython, imageio-ffmpeg)...");
+    log::info!("Upgrading pip/setuptools/wheel and installing build helpers (cython, imageio-ffmpeg)...");
     let mut cmd_upgrade = Command::new(&python_path);
     cmd_upgrade.args(&["-m", "pip", "install", "--upgrade", "pip", "setuptools", "wheel", "cython", "imageio-ffmpeg"]);
     hide_console(&mut cmd_upgrade);
@@ -706,9 +833,7 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
         .env_remove("PYTHONHOME")
         .env_remove("PYTHONPATH")
         .status()
-        .map(|s| if s.success() { println!("Build helpers installed/updated"); } else { println!("Warning: failed to upgrade/install build helpers (exit code: {:?})", s.code()); });
-    
-    append_to_log(&log_path, "Attempting to install build helper packages (pip upgrade etc.)");
+        .map(|s| if s.success() { log::info!("Build helpers installed/updated"); } else { log::warn!("Failed to upgrade/install build helpers (exit code: {:?})", s.code()); });
 
     // Check if faster_whisper is already installed
     let mut cmd_check = Command::new(&python_path);
@@ -721,22 +846,50 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
 
     if let Ok(status) = check_import_status {
         if status.success() {
-            println!("faster_whisper already installed in venv.");
-            append_to_log(&log_path, "faster_whisper already installed in venv.");
+            log::info!("faster_whisper already installed in venv.");
+            if !venv_is_precompiled(&venv_path) {
+                precompile_venv(&python_path, &venv_path);
+            }
             return Ok(venv_path); // Dependencies already installed, return venv_path
         }
     }
 
     // Install dependencies if not already installed
-    println!("Installing transcription dependencies...");
-    append_to_log(&log_path, "Installing transcription dependencies...");
+    log::info!("Installing transcription dependencies...");
+
+    // Check whether the bundled wheelhouse covers every requirement for this
+    // interpreter; if so we can install fully offline instead of requiring
+    // PyPI to be reachable (see `prereflight_check`'s `network_ok` probe).
+    let requirement_lines: Vec<String> = std::fs::read_to_string(&requirements_path)
+        .map(|s| s.lines().map(|l| l.to_string()).collect())
+        .unwrap_or_default();
+    let python_tag = format!("cp{}", python_version.replace(['.', 't'], ""));
+    let wheelhouse_report = wheelhouse::check_wheelhouse(app_handle, &requirement_lines, &python_tag).ok();
+    let offline_install = wheelhouse_report.as_ref().map(|r| r.complete).unwrap_or(false);
+    let wheelhouse_path = wheelhouse::wheelhouse_dir(app_handle).ok().filter(|_| offline_install);
+
+    if let Some(dir) = &wheelhouse_path {
+        log::info!("Bundled wheelhouse covers every requirement for {} - installing natively, no pip/uv invocation needed", python_tag);
+        match install_wheelhouse_natively(dir, &venv_path, &python_path) {
+            Ok(count) => {
+                log::info!("Installed {} wheel(s) from the bundled wheelhouse without invoking pip", count);
+                precompile_venv(&python_path, &venv_path);
+                return Ok(venv_path);
+            }
+            Err(e) => {
+                log::warn!("Native wheelhouse install failed ({}); falling back to uv/pip with --find-links", e);
+            }
+        }
+    }
 
     // Prefer uv for installs. If uv is available, use it exclusively. If uv is not present, fall back to pip routes.
     if uv_available {
-        println!("uv found - installing dependencies using uv...");
-        append_to_log(&log_path, "uv found - installing dependencies using uv...");
+        log::info!("uv found - installing dependencies using uv...");
         let mut cmd_uv_install = Command::new("uv");
         cmd_uv_install.args(&["pip", "install", "-r", &requirements_path.to_string_lossy(), "--python", &venv_path.to_string_lossy()]);
+        if let Some(dir) = &wheelhouse_path {
+            cmd_uv_install.args(&["--find-links", &dir.to_string_lossy(), "--no-index"]);
+        }
         hide_console(&mut cmd_uv_install);
         let status_result = cmd_uv_install
             .env_remove("PYTHONHOME")
@@ -745,34 +898,33 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
 
         match status_result {
             Ok(status) if status.success() => {
-                println!("Successfully installed dependencies with uv");
-                append_to_log(&log_path, "Successfully installed dependencies with uv");
+                log::info!("Successfully installed dependencies with uv");
+                precompile_venv(&python_path, &venv_path);
                 return Ok(venv_path);
             },
             Ok(status) => {
-                println!("uv install failed with exit code: {:?}", status.code());
-                append_to_log(&log_path, &format!("uv failed with exit code: {:?}", status.code()));
+                log::error!("uv failed with exit code: {:?}", status.code());
                 // Do not fall back automatically when uv exists; surface the error and advise user
-                return Err(format!("uv failed to install dependencies (exit code {:?}). Try running 'uv pip install -r {}'.", status.code(), requirements_path.to_string_lossy()));
+                return Err(KortexError::Other(format!("uv failed to install dependencies (exit code {:?}). Try running 'uv pip install -r {}'.", status.code(), requirements_path.to_string_lossy())));
             },
             Err(e) => {
-                println!("Failed to execute uv: {}", e);
-                append_to_log(&log_path, &format!("Failed to execute uv: {}", e));
-                return Err(format!("Failed to execute uv: {}", e));
+                log::error!("Failed to execute uv: {}", e);
+                return Err(KortexError::Other(format!("Failed to execute uv: {}", e)));
             }
         }
     } else {
         // uv not available — run pip-based fallback (prefer-binary first)
-        println!("uv not found - falling back to pip-based installation (prefer-binary)...");
-        append_to_log(&log_path, "uv not found - falling back to pip-based installation (prefer-binary)...");
+        log::info!("uv not found - falling back to pip-based installation (prefer-binary)...");
 
         let mut install_success = false;
 
-        println!("Attempting pip install with --prefer-binary to avoid building C extensions...");
-        append_to_log(&log_path, "Attempting pip install with --prefer-binary to avoid building C extensions...");
+        log::info!("Attempting pip install with --prefer-binary to avoid building C extensions...");
 
         let mut cmd_prefer = Command::new(&python_path);
         cmd_prefer.args(&["-m", "pip", "install", "--prefer-binary", "-r", &requirements_path.to_string_lossy()]);
+        if let Some(dir) = &wheelhouse_path {
+            cmd_prefer.args(&["--find-links", &dir.to_string_lossy(), "--no-index"]);
+        }
         hide_console(&mut cmd_prefer);
         let prefer_binary = cmd_prefer
             .env_remove("PYTHONHOME")
@@ -781,19 +933,14 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
 
         match prefer_binary {
             Ok(output) if output.status.success() => {
-                println!("Successfully installed dependencies with --prefer-binary");
-                append_to_log(&log_path, "Successfully installed dependencies with --prefer-binary");
+                log::info!("Successfully installed dependencies with --prefer-binary");
                 install_success = true;
             }
             Ok(output) => {
-                println!("--prefer-binary install failed, exit code: {:?}", output.status.code());
-                // Save stderr for diagnostics
-                append_to_log(&log_path, &format!("--prefer-binary failed: {}", String::from_utf8_lossy(&output.stderr)));
-                append_to_log(&log_path, &format!("Wrote pip stderr to {:?}", log_path));
+                log::error!("--prefer-binary failed: {}", String::from_utf8_lossy(&output.stderr));
 
                 // Try installing faster-whisper directly with prefer-binary
-                println!("Attempting to install faster-whisper directly with --prefer-binary...");
-                append_to_log(&log_path, "Attempting to install faster-whisper directly with --prefer-binary...");
+                log::info!("Attempting to install faster-whisper directly with --prefer-binary...");
                 let mut cmd_direct = Command::new(&python_path);
                 cmd_direct.args(&["-m", "pip", "install", "--prefer-binary", "faster-whisper"]);
                 hide_console(&mut cmd_direct);
@@ -804,70 +951,161 @@ async fn ensure_transcription_dependencies(app_handle: &tauri::AppHandle) -> Res
 
                 match direct {
                     Ok(out2) if out2.status.success() => {
-                        println!("Successfully installed faster-whisper directly");
-                        append_to_log(&log_path, "Successfully installed faster-whisper directly");
+                        log::info!("Successfully installed faster-whisper directly");
                         install_success = true;
                     }
                     Ok(out2) => {
-                        append_to_log(&log_path, &format!("Direct install failed: {}", String::from_utf8_lossy(&out2.stderr)));
-                        append_to_log(&log_path, "Direct install also failed");
+                        log::error!("Direct install also failed: {}", String::from_utf8_lossy(&out2.stderr));
                     }
                     Err(e) => {
-                        println!("Failed to execute pip for direct install: {}", e);
-                        append_to_log(&log_path, &format!("Failed to execute pip for direct install: {}", e));
+                        log::error!("Failed to execute pip for direct install: {}", e);
                     }
                 }
             }
             Err(e) => {
-                println!("Failed to execute pip (prefer-binary): {}", e);
-                append_to_log(&log_path, &format!("Failed to execute pip (prefer-binary): {}", e));
+                log::error!("Failed to execute pip (prefer-binary): {}", e);
             }
         }
 
         if install_success {
-            append_to_log(&log_path, "Installation complete (prefer-binary route)");
+            log::info!("Installation complete (prefer-binary route)");
+            precompile_venv(&python_path, &venv_path);
             Ok(venv_path)
         } else {
-            // Provide actionable guidance in the error message and point to log file
-            let log_path = app_data_dir.join("transcription_install.log");
+            // Provide actionable guidance in the error message
             let guidance = "If you see build errors for 'av' (PyAV) on Windows, try one of the following:\n"
                 .to_string()
                 + " 1) Install Microsoft Visual C++ Build Tools (Visual Studio C++ workload) and FFmpeg development headers, then retry.\n"
                 + " 2) Install a prebuilt PyAV wheel matching your Python version (e.g., from https://www.lfd.uci.edu/~gohlke/pythonlibs/) or use conda: 'conda install -c conda-forge av ffmpeg'.\n"
                 + " 3) Run 'pip install --prefer-binary -r requirements.txt' manually to prefer wheels.\n"
-                + "Logs from pip were written to: ";
+                + "See Settings > Logs for the full install log.";
+
+            log::error!("Installation failed - see logs above");
+
+            Err(KortexError::Other(format!("Failed to install transcription dependencies. {}", guidance)))
+        }
+    }
+}
+
+/// One record of progress on an in-flight transcription job, emitted on the
+/// `transcription-progress` event so the frontend can drive a live progress
+/// bar without waiting on the command's promise. `transcribe.py` is expected
+/// to print one of these (minus `job_id`, which we attach) as a line of JSON
+/// to stdout for each update, and a final `{"type": "result", ...}` record
+/// shaped like `TranscriptionResult` before exiting.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TranscriptionProgressEvent {
+    Started { job_id: String },
+    Progress { job_id: String, percent: Option<f64>, timestamp: Option<f64>, text: Option<String> },
+    Completed { job_id: String, result: serde_json::Value },
+    Failed { job_id: String, error: String },
+}
 
-            append_to_log(&log_path, "Installation failed - see logs above");
+/// Transcription jobs currently running, keyed by the job id handed to the
+/// frontend in the `Started` event, so `cancel_transcription` can kill the
+/// right child process.
+static TRANSCRIPTION_JOBS: std::sync::OnceLock<std::sync::Mutex<std::collections::HashMap<String, std::process::Child>>> =
+    std::sync::OnceLock::new();
 
-            Err(format!("Failed to install transcription dependencies. {} Log: {:?}", guidance, log_path))
+#[tauri::command]
+async fn cancel_transcription(job_id: String) -> Result<(), String> {
+    let jobs = TRANSCRIPTION_JOBS.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+    let mut guard = jobs.lock().map_err(|e| format!("Mutex error: {}", e))?;
+    match guard.remove(&job_id) {
+        Some(mut child) => {
+            child.kill().map_err(|e| format!("Failed to kill transcription job {}: {}", job_id, e))?;
+            log::info!("Cancelled transcription job {}", job_id);
+            Ok(())
         }
+        None => Err(format!("No active transcription job with id {}", job_id)),
     }
 }
 
+/// Transcribes `audio_path` and returns a JSON-encoded `TranscriptionResult`.
+/// Runs the native candle-transformers Whisper pipeline
+/// ([`audio::transcription::whisper::transcribe_native`]) by default; the
+/// old venv/`transcribe.py` subprocess path only exists when built with the
+/// `python-transcription` feature, for environments where a model
+/// checkpoint hasn't been downloaded yet.
 #[tauri::command]
 async fn transcribe_audio(audio_path: String, app_handle: tauri::AppHandle) -> Result<String, String> {
-    use std::process::Command;
+    #[cfg(not(feature = "python-transcription"))]
+    {
+        transcribe_audio_native(audio_path, app_handle).await
+    }
+    #[cfg(feature = "python-transcription")]
+    {
+        transcribe_audio_python(audio_path, app_handle).await
+    }
+}
+
+/// Native, in-process transcription. No child process, no progress stream
+/// from an external script — the `Started`/`Completed` events are emitted
+/// around a single blocking call so the frontend's existing
+/// `transcription-progress` listener keeps working either way.
+async fn transcribe_audio_native(audio_path: String, app_handle: tauri::AppHandle) -> Result<String, String> {
+    use tauri::Emitter;
+
+    let job_id = Uuid::new_v4().to_string();
+    let _ = app_handle.emit(
+        "transcription-progress",
+        TranscriptionProgressEvent::Started { job_id: job_id.clone() },
+    );
+
+    let handle = app_handle.clone();
+    let path = audio_path.clone();
+    let result = tokio::task::spawn_blocking(move || audio::transcription::whisper::transcribe_native(&handle, &path))
+        .await
+        .map_err(|e| format!("Transcription task panicked: {}", e))?;
+
+    match result {
+        Ok(transcription) => {
+            let value = serde_json::to_value(&transcription).map_err(|e| format!("Serialization error: {}", e))?;
+            let _ = app_handle.emit(
+                "transcription-progress",
+                TranscriptionProgressEvent::Completed { job_id: job_id.clone(), result: value.clone() },
+            );
+            serde_json::to_string(&value).map_err(|e| format!("Serialization error: {}", e))
+        }
+        Err(error) => {
+            let _ = app_handle.emit(
+                "transcription-progress",
+                TranscriptionProgressEvent::Failed { job_id: job_id.clone(), error: error.clone() },
+            );
+            Err(error)
+        }
+    }
+}
+
+#[cfg(feature = "python-transcription")]
+async fn transcribe_audio_python(audio_path: String, app_handle: tauri::AppHandle) -> Result<String, String> {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
     use tauri::path::BaseDirectory;
+    use tauri::Emitter;
 
-    let venv_path = ensure_transcription_dependencies(&app_handle).await?;
+    let venv_path = ensure_transcription_dependencies(&app_handle, None).await?;
 
     let script_path = app_handle.path().resolve("src/audio/transcription/transcribe.py", BaseDirectory::Resource)
         .map_err(|e| format!("Failed to resolve transcribe.py resource: {}", e))?;
-    
+
     let python_path = python_executable_in_venv(&venv_path);
 
     if !python_path.exists() {
         return Err("Python executable not found in venv".to_string());
     }
 
+    let job_id = Uuid::new_v4().to_string();
+
     // Spawn the transcription script without creating a console window on Windows
     let mut cmd = Command::new(&python_path);
     cmd.arg(&script_path)
         .arg(&audio_path)
         .env_remove("PYTHONHOME")
         .env_remove("PYTHONPATH")
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped());
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
 
     #[cfg(windows)]
     {
@@ -875,35 +1113,103 @@ async fn transcribe_audio(audio_path: String, app_handle: tauri::AppHandle) -> R
     cmd.creation_flags(0x08000000);
     }
 
-    let child = cmd
+    let mut child = cmd
         .spawn()
         .map_err(|e| format!("Failed to spawn transcription script: {}", e))?;
 
-    let output = child
-        .wait_with_output()
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "Failed to capture transcription script stdout".to_string())?;
+
+    // Drain stderr on its own thread rather than piping it into the same
+    // read loop as stdout, so a chatty script can't deadlock the pipe.
+    if let Some(stderr) = child.stderr.take() {
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                log::warn!("transcribe.py: {}", line);
+            }
+        });
+    }
+
+    let _ = app_handle.emit(
+        "transcription-progress",
+        TranscriptionProgressEvent::Started { job_id: job_id.clone() },
+    );
+
+    let jobs = TRANSCRIPTION_JOBS.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+    jobs.lock().map_err(|e| format!("Mutex error: {}", e))?.insert(job_id.clone(), child);
+
+    let mut final_result: Option<serde_json::Value> = None;
+    let mut last_line = String::new();
+
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        last_line = line.clone();
+
+        let Ok(record) = serde_json::from_str::<serde_json::Value>(&line) else {
+            continue; // tolerate stray non-JSON output lines
+        };
+
+        match record.get("type").and_then(|v| v.as_str()) {
+            Some("result") => final_result = Some(record),
+            _ => {
+                let percent = record.get("percent").and_then(|v| v.as_f64());
+                let timestamp = record.get("timestamp").and_then(|v| v.as_f64());
+                let text = record.get("text").and_then(|v| v.as_str()).map(|s| s.to_string());
+                let _ = app_handle.emit(
+                    "transcription-progress",
+                    TranscriptionProgressEvent::Progress { job_id: job_id.clone(), percent, timestamp, text },
+                );
+            }
+        }
+    }
+
+    let mut child = jobs
+        .lock()
+        .map_err(|e| format!("Mutex error: {}", e))?
+        .remove(&job_id)
+        .ok_or_else(|| format!("Transcription job {} was cancelled", job_id))?;
+    let status = child
+        .wait()
         .map_err(|e| format!("Failed to wait for transcription script: {}", e))?;
 
-    if output.status.success() {
-        let stdout = String::from_utf8(output.stdout)
-            .map_err(|e| format!("Invalid output encoding: {}", e))?;
+    if !status.success() {
+        let error = format!("Transcription script failed (exit code {:?})", status.code());
+        let _ = app_handle.emit(
+            "transcription-progress",
+            TranscriptionProgressEvent::Failed { job_id: job_id.clone(), error: error.clone() },
+        );
+        return Err(error);
+    }
 
-        // Try to parse as JSON
-        if let Ok(result) = serde_json::from_str::<serde_json::Value>(&stdout) {
-            return serde_json::to_string(&result)
-                .map_err(|e| format!("Serialization error: {}", e));
-        } else {
-            return Err(format!("Invalid JSON output: {}", stdout));
+    match final_result {
+        Some(result) => {
+            let _ = app_handle.emit(
+                "transcription-progress",
+                TranscriptionProgressEvent::Completed { job_id: job_id.clone(), result: result.clone() },
+            );
+            serde_json::to_string(&result).map_err(|e| format!("Serialization error: {}", e))
+        }
+        None => {
+            let error = format!("Invalid JSON output: {}", last_line);
+            let _ = app_handle.emit(
+                "transcription-progress",
+                TranscriptionProgressEvent::Failed { job_id: job_id.clone(), error: error.clone() },
+            );
+            Err(error)
         }
-    } else {
-        // stderr is inherited so it's already printed, but we can't capture it here for the error message
-        // unless we pipe it. But inheriting is better for UX.
-        return Err("Transcription script failed (check terminal logs for details)".to_string());
     }
 }
 
 #[tauri::command]
-async fn install_transcription_dependencies(app_handle: tauri::AppHandle) -> Result<(), String> {
-    let _venv_path = ensure_transcription_dependencies(&app_handle).await?;
+async fn install_transcription_dependencies(
+    app_handle: tauri::AppHandle,
+    python_version: Option<String>,
+) -> Result<(), String> {
+    let _venv_path = ensure_transcription_dependencies(&app_handle, python_version.as_deref()).await?;
     Ok(())
 }
 
@@ -912,120 +1218,111 @@ async fn install_system_dependencies(app_handle: tauri::AppHandle) -> Result<ser
     use std::process::Command;
     use tauri::path::BaseDirectory;
 
-    let app_data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    let log_path = app_data_dir.join("transcription_install.log");
-    fn append_to_log(path: &std::path::PathBuf, msg: &str) {
-        use std::io::Write;
-        if let Ok(mut f) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
-            let _ = f.write_all(msg.as_bytes());
-            let _ = f.write_all(b"\n");
-        }
-    }
-
-    append_to_log(&log_path, "Starting system dependency installer (best-effort)");
+    log::info!("Starting system dependency installer (best-effort)");
 
     let mut results = serde_json::Map::new();
 
+    // Python itself is no longer acquired through OS package managers: uv's
+    // managed-interpreter support (see `ensure_transcription_dependencies`)
+    // downloads a self-contained CPython build directly, so the only system
+    // dependency left to bootstrap here is ffmpeg (and uv itself, if absent).
     if cfg!(windows) {
-        // Try winget first
-        append_to_log(&log_path, "Windows detected: trying winget to install Python and ffmpeg");
-        let mut cmd_w_py = Command::new("winget");
-        cmd_w_py.args(&["install", "--id", "Python.Python.3", "-e", "--silent"]);
-        hide_console(&mut cmd_w_py);
-        let winget_py = cmd_w_py.status();
-        append_to_log(&log_path, &format!("winget python status: {:?}", winget_py));
+        log::info!("Windows detected: trying winget to install ffmpeg");
         let mut cmd_w_ff = Command::new("winget");
         cmd_w_ff.args(&["install", "--id", "Gyan.FFmpeg", "-e", "--silent"]);
         hide_console(&mut cmd_w_ff);
         let winget_ff = cmd_w_ff.status();
-        append_to_log(&log_path, &format!("winget ffmpeg status: {:?}", winget_ff));
+        log::info!("winget ffmpeg status: {:?}", winget_ff);
 
         // Fallback to choco if winget not present
-        let mut cmd_ch_py = Command::new("choco");
-        cmd_ch_py.args(&["install", "python", "-y"]);
-        hide_console(&mut cmd_ch_py);
-        let choco_py = cmd_ch_py.status();
-        append_to_log(&log_path, &format!("choco python status: {:?}", choco_py));
         let mut cmd_ch_ff = Command::new("choco");
         cmd_ch_ff.args(&["install", "ffmpeg", "-y"]);
         hide_console(&mut cmd_ch_ff);
         let choco_ff = cmd_ch_ff.status();
-        append_to_log(&log_path, &format!("choco ffmpeg status: {:?}", choco_ff));
+        log::info!("choco ffmpeg status: {:?}", choco_ff);
 
         // Installer cannot reliably install Visual C++ redistributable automatically; link user instead
-        results.insert("python_attempted".to_string(), serde_json::Value::Bool(true));
         results.insert("ffmpeg_attempted".to_string(), serde_json::Value::Bool(true));
         results.insert("vcruntime_note".to_string(), serde_json::Value::String("Install Visual C++ Redistributable manually from Microsoft if needed".to_string()));
     } else if cfg!(target_os = "macos") {
-        append_to_log(&log_path, "macOS detected: trying brew to install python and ffmpeg");
-        let mut cmd_brew_py = Command::new("brew");
-        cmd_brew_py.args(&["install", "python"]);
-        hide_console(&mut cmd_brew_py);
-        let brew_py = cmd_brew_py.status();
-        append_to_log(&log_path, &format!("brew python status: {:?}", brew_py));
+        log::info!("macOS detected: trying brew to install ffmpeg");
         let mut cmd_brew_ff = Command::new("brew");
         cmd_brew_ff.args(&["install", "ffmpeg"]);
         hide_console(&mut cmd_brew_ff);
         let brew_ff = cmd_brew_ff.status();
-        append_to_log(&log_path, &format!("brew ffmpeg status: {:?}", brew_ff));
-        results.insert("python_attempted".to_string(), serde_json::Value::Bool(true));
+        log::info!("brew ffmpeg status: {:?}", brew_ff);
         results.insert("ffmpeg_attempted".to_string(), serde_json::Value::Bool(true));
     } else {
         // Assume linux
-        append_to_log(&log_path, "Linux detected: trying apt/dnf/pacman to install python3 and ffmpeg");
+        log::info!("Linux detected: trying apt/dnf/pacman to install ffmpeg");
         let mut cmd_apt = Command::new("sh");
-        cmd_apt.args(&["-c", "apt-get update && apt-get install -y python3 python3-pip ffmpeg"]);
+        cmd_apt.args(&["-c", "apt-get update && apt-get install -y ffmpeg"]);
         hide_console(&mut cmd_apt);
         let apt_update = cmd_apt.status();
-        append_to_log(&log_path, &format!("apt status: {:?}", apt_update));
+        log::info!("apt status: {:?}", apt_update);
         let mut cmd_dnf = Command::new("sh");
-        cmd_dnf.args(&["-c", "dnf install -y python3 python3-pip ffmpeg"]);
+        cmd_dnf.args(&["-c", "dnf install -y ffmpeg"]);
         hide_console(&mut cmd_dnf);
         let dnf = cmd_dnf.status();
-        append_to_log(&log_path, &format!("dnf status: {:?}", dnf));
+        log::info!("dnf status: {:?}", dnf);
         let mut cmd_pac = Command::new("sh");
-        cmd_pac.args(&["-c", "pacman -S --noconfirm python python-pip ffmpeg"]);
+        cmd_pac.args(&["-c", "pacman -S --noconfirm ffmpeg"]);
         hide_console(&mut cmd_pac);
         let pacman = cmd_pac.status();
-        append_to_log(&log_path, &format!("pacman status: {:?}", pacman));
-        results.insert("python_attempted".to_string(), serde_json::Value::Bool(true));
+        log::info!("pacman status: {:?}", pacman);
         results.insert("ffmpeg_attempted".to_string(), serde_json::Value::Bool(true));
     }
 
-    append_to_log(&log_path, "System dependency installer finished (check OS package manager output above)");
+    // uv is the prerequisite for the managed-Python bootstrap; install it if missing.
+    let uv_present = Command::new("uv").arg("--version").status().map(|s| s.success()).unwrap_or(false);
+    if !uv_present {
+        log::info!("uv not found - attempting to install uv...");
+        let uv_installed = if cfg!(windows) {
+            let mut cmd = Command::new("powershell");
+            cmd.args(&["-ExecutionPolicy", "ByPass", "-c", "irm https://astral.sh/uv/install.ps1 | iex"]);
+            hide_console(&mut cmd);
+            cmd.status().map(|s| s.success()).unwrap_or(false)
+        } else {
+            let mut cmd = Command::new("sh");
+            cmd.args(&["-c", "curl -LsSf https://astral.sh/uv/install.sh | sh"]);
+            hide_console(&mut cmd);
+            cmd.status().map(|s| s.success()).unwrap_or(false)
+        };
+        log::info!("uv install: {}", uv_installed);
+        results.insert("uv_attempted".to_string(), serde_json::Value::Bool(true));
+    }
+
+    log::info!("System dependency installer finished (check package manager output above)");
 
     // Try installing Rust toolchain if pip builds require it
-    append_to_log(&log_path, "Checking Rust toolchain (needed for building some Python wheels)...");
+    log::info!("Checking Rust toolchain (needed for building some Python wheels)...");
     let mut need_rust = true;
     if let Ok(status) = Command::new("rustc").arg("--version").status() {
         if status.success() { need_rust = false; }
     }
     if need_rust {
-        append_to_log(&log_path, "Rust not found - attempting to install rust toolchain...");
+        log::info!("Rust not found - attempting to install rust toolchain...");
         if cfg!(windows) {
             let mut cmd_r = Command::new("winget");
             cmd_r.args(&["install", "--id", "RustLang.Rust", "-e", "--silent"]);
             hide_console(&mut cmd_r);
             let r = cmd_r.status();
-            append_to_log(&log_path, &format!("winget rust status: {:?}", r));
+            log::info!("winget rust status: {:?}", r);
         } else if cfg!(target_os = "macos") {
             let mut cmd_r = Command::new("brew");
             cmd_r.args(&["install", "rust"]);
             hide_console(&mut cmd_r);
             let r = cmd_r.status();
-            append_to_log(&log_path, &format!("brew rust status: {:?}", r));
+            log::info!("brew rust status: {:?}", r);
         } else {
             let mut cmd_r = Command::new("sh");
             cmd_r.args(&["-c", "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y"]);
             hide_console(&mut cmd_r);
             let r = cmd_r.status();
-            append_to_log(&log_path, &format!("rustup install status: {:?}", r));
+            log::info!("rustup install status: {:?}", r);
         }
     } else {
-        append_to_log(&log_path, "Rust toolchain already present");
+        log::info!("Rust toolchain already present");
     }
 
     Ok(serde_json::Value::Object(results))
@@ -1043,43 +1340,52 @@ async fn prereflight_check(app_handle: tauri::AppHandle) -> Result<serde_json::V
     // Platform
     map.insert("platform".to_string(), serde_json::Value::String(std::env::consts::OS.to_string()));
 
-    // Check for Python (try py launcher on Windows first, then python)
-    let mut python_found = false;
-    let mut python_version: Option<String> = None;
-    let mut python_exec: Option<String> = None;
-
-    let try_python_cmd = |cmd: &str, args: &[&str]| -> Option<(String, String)> {
-        if let Ok(output) = Command::new(cmd).args(args).output() {
-            if output.status.success() {
-                let out = String::from_utf8_lossy(&output.stdout).to_string();
-                let mut lines = out.lines();
-                let exe = lines.next().map(|s| s.to_string()).unwrap_or_default();
-                let ver = lines.next().map(|s| s.to_string()).unwrap_or_default();
-                return Some((exe, ver));
-            }
-        }
-        None
-    };
-
-    if cfg!(windows) {
-        if let Some((exe, ver)) = try_python_cmd("py", &["-3", "-c", "import sys;print(sys.executable);print(sys.version)"]) {
-            python_found = true;
-            python_exec = Some(exe);
-            python_version = Some(ver);
-        }
-    }
-
-    if !python_found {
-        if let Some((exe, ver)) = try_python_cmd("python", &["-c", "import sys;print(sys.executable);print(sys.version)"]) {
-            python_found = true;
-            python_exec = Some(exe);
-            python_version = Some(ver);
-        }
-    }
+    // Enumerate every interpreter we can find (py launcher list, uv-managed,
+    // PATH) and rank them against faster-whisper's supported version range,
+    // rather than blindly trusting whatever `python` resolves to first.
+    const PYTHON_CONSTRAINT: &str = ">=3.9,<3.13";
+    let python_candidates = python_discovery::discover_candidates(PYTHON_CONSTRAINT);
+    let best_candidate = python_candidates.iter().find(|c| c.satisfies_constraint);
+
+    map.insert("python_found".to_string(), serde_json::Value::Bool(best_candidate.is_some()));
+    map.insert(
+        "python_version".to_string(),
+        match best_candidate {
+            Some(c) => serde_json::Value::String(c.raw_version.clone()),
+            None => serde_json::Value::Null,
+        },
+    );
+    map.insert(
+        "python_executable".to_string(),
+        match best_candidate {
+            Some(c) => serde_json::Value::String(c.executable.clone()),
+            None => serde_json::Value::Null,
+        },
+    );
+    map.insert(
+        "python_candidates".to_string(),
+        serde_json::to_value(&python_candidates).unwrap_or(serde_json::Value::Array(vec![])),
+    );
+
+    // Even with no system Python, uv can download its own managed CPython
+    // build on demand, so the absence of `python_found` isn't fatal as long
+    // as uv is present.
+    let uv_available = Command::new("uv")
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+    map.insert("uv_available".to_string(), serde_json::Value::Bool(uv_available));
+    map.insert("managed_python_available".to_string(), serde_json::Value::Bool(uv_available));
 
-    map.insert("python_found".to_string(), serde_json::Value::Bool(python_found));
-    map.insert("python_version".to_string(), match python_version { Some(v) => serde_json::Value::String(v), None => serde_json::Value::Null });
-    map.insert("python_executable".to_string(), match python_exec { Some(p) => serde_json::Value::String(p), None => serde_json::Value::Null });
+    // Lets the UI show "optimizing for first run" instead of re-warning about
+    // install state once dependencies are in and the bytecode cache is warm.
+    let venv_precompiled = app_handle
+        .path()
+        .app_data_dir()
+        .map(|dir| venv_is_precompiled(&dir.join("transcription_venv")))
+        .unwrap_or(false);
+    map.insert("venv_precompiled".to_string(), serde_json::Value::Bool(venv_precompiled));
 
     // Check ffmpeg availability
     let ffmpeg_available = if let Ok(output) = Command::new("ffmpeg").arg("-version").output() {
@@ -1122,6 +1428,105 @@ async fn prereflight_check(app_handle: tauri::AppHandle) -> Result<serde_json::V
     Ok(serde_json::Value::Object(map))
 }
 
+/// One command-line tool (uv or a Python launcher) probed for diagnostics,
+/// with the first line of its version output if it ran.
+#[derive(Debug, Serialize)]
+struct ToolVersion {
+    name: String,
+    on_path: bool,
+    version: Option<String>,
+}
+
+/// Everything the settings UI needs to render a transcription setup
+/// diagnostics panel without re-deriving it from scattered `println!`s.
+#[derive(Debug, Serialize)]
+struct TranscriptionDiagnostics {
+    tools: Vec<ToolVersion>,
+    venv_path: String,
+    venv_exists: bool,
+    venv_precompiled: bool,
+    install_log_tail: Vec<String>,
+    requirements: Vec<String>,
+}
+
+fn probe_tool_version(name: &str, args: &[&str]) -> ToolVersion {
+    let mut cmd = Command::new(name);
+    cmd.args(args);
+    hide_console(&mut cmd);
+
+    match cmd.output() {
+        Ok(output) if output.status.success() => {
+            let combined = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            let version = combined.lines().next().map(|l| l.trim().to_string());
+            ToolVersion { name: name.to_string(), on_path: true, version }
+        }
+        _ => ToolVersion { name: name.to_string(), on_path: false, version: None },
+    }
+}
+
+#[tauri::command]
+async fn get_transcription_diagnostics(app_handle: tauri::AppHandle) -> KortexResult<TranscriptionDiagnostics> {
+    let tools = vec![
+        probe_tool_version("uv", &["--version"]),
+        probe_tool_version("py", &["-3", "--version"]),
+        probe_tool_version("python3", &["--version"]),
+        probe_tool_version("python", &["--version"]),
+    ];
+
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| KortexError::PathResolution(format!("Failed to get app data directory: {}", e)))?;
+    let venv_path = app_data_dir.join("transcription_venv");
+
+    let install_log_tail = logging::tail(50);
+
+    let requirements_path = app_handle
+        .path()
+        .resolve("src/audio/transcription/requirements.txt", BaseDirectory::Resource)
+        .map_err(|e| KortexError::PathResolution(format!("Failed to resolve requirements.txt resource: {}", e)))?;
+    let requirements = if requirements_path.exists() {
+        fs::read_to_string(&requirements_path)?
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(TranscriptionDiagnostics {
+        tools,
+        venv_exists: venv_path.exists(),
+        venv_precompiled: venv_is_precompiled(&venv_path),
+        venv_path: venv_path.to_string_lossy().to_string(),
+        install_log_tail,
+        requirements,
+    })
+}
+
+/// Report which of the transcription requirements have a bundled, tag-compatible
+/// wheel for the default managed Python version, so the UI can warn before the
+/// user goes offline that the bundled wheelhouse wouldn't cover a full install.
+#[tauri::command]
+async fn get_wheelhouse_status(app_handle: tauri::AppHandle) -> KortexResult<wheelhouse::WheelhouseReport> {
+    let requirements_path = app_handle
+        .path()
+        .resolve("src/audio/transcription/requirements.txt", BaseDirectory::Resource)
+        .map_err(|e| KortexError::PathResolution(format!("Failed to resolve requirements.txt resource: {}", e)))?;
+    let requirement_lines: Vec<String> = fs::read_to_string(&requirements_path)?
+        .lines()
+        .map(|l| l.to_string())
+        .collect();
+    let python_tag = format!("cp{}", DEFAULT_MANAGED_PYTHON_VERSION.replace('.', ""));
+
+    wheelhouse::check_wheelhouse(&app_handle, &requirement_lines, &python_tag)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Load .env file
@@ -1129,6 +1534,13 @@ pub fn run() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_fs::init())
+        .manage(google_drive::GoogleDriveState::new())
+        .setup(|app| {
+            if let Err(e) = logging::init(&app.handle().clone()) {
+                eprintln!("Failed to initialize logging: {}", e);
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             get_notes_path,
             create_note,
@@ -1150,25 +1562,36 @@ pub fn run() {
             start_recording,
             stop_recording,
             transcribe_audio,
+            cancel_transcription,
             prereflight_check,
-            read_install_log
+            logging::tail_logs,
+            backup::create_snapshot,
+            backup::restore_snapshot,
+            backup::prune_snapshots,
+            get_transcription_diagnostics,
+            get_wheelhouse_status,
+            google_drive::connect_google_drive,
+            google_drive::get_google_drive_status,
+            google_drive::disconnect_google_drive,
+            google_drive::list_shared_drives,
+            google_drive::select_shared_drive,
+            google_drive::list_note_permissions,
+            google_drive::share_drive_note,
+            google_drive::unshare_drive_note,
+            google_drive::sync_notes_to_google_drive,
+            google_drive::sync_notes_to_google_drive_incremental,
+            google_drive::check_sync_status,
+            google_drive::force_sync_from_cloud,
+            google_drive::force_sync_to_cloud,
+            google_drive::sync_all_to_google_drive,
+            google_drive::cleanup_old_trash,
+            google_drive::get_sync_plan,
+            google_drive::start_sync,
+            google_drive::resume_sync,
+            google_drive::pause_sync,
+            google_drive::execute_sync_with_resolutions
         ])
         .plugin(tauri_plugin_opener::init())
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
-
-#[tauri::command]
-async fn read_install_log(app_handle: tauri::AppHandle) -> Result<String, String> {
-    // Return contents of the transcription_install.log in the app data directory (best-effort)
-    let app_data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    let log_path = app_data_dir.join("transcription_install.log");
-    if log_path.exists() {
-        std::fs::read_to_string(&log_path).map_err(|e| format!("Failed to read log file: {}", e))
-    } else {
-        Ok(String::new())
-    }
-}
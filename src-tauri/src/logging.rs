@@ -0,0 +1,121 @@
+//! File-backed logging built on the `log` crate, replacing the ad-hoc
+//! `append_to_log`/`println!` pattern scattered through the transcription
+//! install path. Every record is appended to a size-rotated file under
+//! `AppConfig/Kortex/logs` and re-emitted as a `log-line` Tauri event so the
+//! frontend can show live install/recording progress.
+
+use log::{LevelFilter, Log, Metadata, Record};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Emitter, Manager};
+
+const LOG_FILE_NAME: &str = "logia.log";
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+struct FileLogger {
+    file: Mutex<File>,
+    path: PathBuf,
+    app_handle: AppHandle,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= LevelFilter::Info
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!(
+            "[{}] {} {}: {}",
+            chrono::Utc::now().to_rfc3339(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+
+        if let Ok(mut file) = self.file.lock() {
+            rotate_if_needed(&mut file, &self.path);
+            let _ = writeln!(file, "{}", line);
+        }
+
+        let _ = self.app_handle.emit("log-line", &line);
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+fn rotate_if_needed(file: &mut File, path: &PathBuf) {
+    if let Ok(metadata) = file.metadata() {
+        if metadata.len() > MAX_LOG_BYTES {
+            let rotated = path.with_extension("log.1");
+            let _ = fs::rename(path, &rotated);
+            if let Ok(new_file) = OpenOptions::new().create(true).append(true).open(path) {
+                *file = new_file;
+            }
+        }
+    }
+}
+
+/// Resolve the logs directory (`AppConfig/Kortex/logs`), creating it if needed.
+fn logs_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .resolve("Kortex/logs", BaseDirectory::AppConfig)
+        .map_err(|e| format!("Could not resolve logs directory: {}", e))?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create logs directory: {}", e))?;
+    Ok(dir)
+}
+
+/// Initialize the global file logger. Call once during app setup; later
+/// calls return an error (the `log` crate only allows one logger).
+pub fn init(app_handle: &AppHandle) -> Result<(), String> {
+    let dir = logs_dir(app_handle)?;
+    let path = dir.join(LOG_FILE_NAME);
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open log file: {}", e))?;
+
+    let _ = LOG_PATH.set(path.clone());
+
+    let logger = FileLogger { file: Mutex::new(file), path, app_handle: app_handle.clone() };
+
+    log::set_boxed_logger(Box::new(logger))
+        .map(|_| log::set_max_level(LevelFilter::Info))
+        .map_err(|e| format!("Logger already initialized: {}", e))
+}
+
+/// Read the last `lines` lines of the current log file. Used both by the
+/// `tail_logs` command and by diagnostics code that wants a synchronous tail.
+pub fn tail(lines: usize) -> Vec<String> {
+    let Some(path) = LOG_PATH.get() else {
+        return Vec::new();
+    };
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    let all_lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let start = all_lines.len().saturating_sub(lines);
+    all_lines[start..].to_vec()
+}
+
+/// Return the last `lines` lines of the current log file.
+#[tauri::command]
+pub async fn tail_logs(lines: usize) -> Result<Vec<String>, String> {
+    Ok(tail(lines))
+}
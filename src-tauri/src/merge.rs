@@ -0,0 +1,250 @@
+//! Three-way merge for the structured JSON notes/kanban files synced by
+//! `sync_manifest`/`google_drive`, so a file edited on both sides doesn't
+//! automatically become a `FileStatus::Conflict` the user has to resolve by
+//! hand. Diffs `local` and `cloud` against their common `ancestor`: object
+//! keys merge union-wise, a field changed on only one side takes that
+//! side's value, a field changed identically on both sides is a no-op, and
+//! entity arrays (objects with an `id`, e.g. kanban cards) merge as
+//! add-wins sets. Only a genuine edit of the same non-empty text field to
+//! two different values is left as a real conflict.
+
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Outcome of merging one JSON value (or subtree) three ways.
+pub enum MergeResult {
+    /// Merge succeeded; no unresolvable conflicting edit was found.
+    Merged(Value),
+    /// Both sides edited the same text field to different non-empty
+    /// values — requires the user to pick one.
+    Conflict,
+}
+
+/// Merge `local` and `cloud`, both derived from `ancestor`, into one value.
+/// `local_wins_ties` decides the last-writer-wins fallback for scalar
+/// fields that changed on both sides but aren't the kind of text edit that
+/// should stay a real conflict (typically `local_modified >= cloud_modified`).
+pub fn three_way_merge(ancestor: &Value, local: &Value, cloud: &Value, local_wins_ties: bool) -> MergeResult {
+    if local == cloud {
+        return MergeResult::Merged(local.clone());
+    }
+    // Entity arrays skip the one-side-unchanged shortcuts: a card deleted on
+    // one side still survives if the other side kept it.
+    let entity_arrays = matches!((local, cloud), (Value::Array(l), Value::Array(c)) if is_entity_array(l) || is_entity_array(c));
+    if local == ancestor && !entity_arrays {
+        return MergeResult::Merged(cloud.clone());
+    }
+    if cloud == ancestor && !entity_arrays {
+        return MergeResult::Merged(local.clone());
+    }
+
+    match (local, cloud) {
+        (Value::Object(local_map), Value::Object(cloud_map)) => merge_objects(ancestor.as_object(), local_map, cloud_map, local_wins_ties),
+        (Value::Array(local_arr), Value::Array(cloud_arr)) if is_entity_array(local_arr) || is_entity_array(cloud_arr) => {
+            let empty = Vec::new();
+            let ancestor_arr = ancestor.as_array().unwrap_or(&empty);
+            match merge_entity_arrays(ancestor_arr, local_arr, cloud_arr, local_wins_ties) {
+                Some(merged) => MergeResult::Merged(Value::Array(merged)),
+                None => MergeResult::Conflict,
+            }
+        }
+        (Value::String(l), Value::String(c)) => {
+            if !l.is_empty() && !c.is_empty() {
+                // Both sides wrote a different, non-empty text value — this is
+                // the one case that stays a real conflict for the user.
+                MergeResult::Conflict
+            } else {
+                MergeResult::Merged(if local_wins_ties { local.clone() } else { cloud.clone() })
+            }
+        }
+        // Divergent non-text scalars (numbers, bools, nulls, mismatched
+        // types): last-writer-wins rather than surfacing a conflict.
+        _ => MergeResult::Merged(if local_wins_ties { local.clone() } else { cloud.clone() }),
+    }
+}
+
+fn merge_objects(
+    ancestor_map: Option<&Map<String, Value>>,
+    local_map: &Map<String, Value>,
+    cloud_map: &Map<String, Value>,
+    local_wins_ties: bool,
+) -> MergeResult {
+    let empty = Map::new();
+    let ancestor_map = ancestor_map.unwrap_or(&empty);
+
+    let mut keys: Vec<&String> = ancestor_map.keys().chain(local_map.keys()).chain(cloud_map.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut merged = Map::new();
+    for key in keys {
+        let a = ancestor_map.get(key).cloned().unwrap_or(Value::Null);
+        let l = local_map.get(key).cloned().unwrap_or(Value::Null);
+        let c = cloud_map.get(key).cloned().unwrap_or(Value::Null);
+
+        match three_way_merge(&a, &l, &c, local_wins_ties) {
+            MergeResult::Merged(v) => {
+                if !v.is_null() {
+                    merged.insert(key.clone(), v);
+                }
+            }
+            MergeResult::Conflict => return MergeResult::Conflict,
+        }
+    }
+
+    MergeResult::Merged(Value::Object(merged))
+}
+
+/// An array is treated as an add-wins set of entities (e.g. kanban cards or
+/// tags) when every element is an object carrying an `id`.
+fn is_entity_array(arr: &[Value]) -> bool {
+    !arr.is_empty() && arr.iter().all(|v| v.as_object().map(|o| o.contains_key("id")).unwrap_or(false))
+}
+
+fn entity_id(v: &Value) -> Option<String> {
+    v.get("id").map(value_as_id_string)
+}
+
+fn value_as_id_string(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Merge two entity arrays against their common ancestor. A card present in
+/// either side and not deleted in both survives; deletion only wins when
+/// neither side re-added (or kept) the same id.
+fn merge_entity_arrays(ancestor: &[Value], local: &[Value], cloud: &[Value], local_wins_ties: bool) -> Option<Vec<Value>> {
+    let index_by_id = |arr: &[Value]| -> HashMap<String, Value> {
+        arr.iter().filter_map(|v| entity_id(v).map(|id| (id, v.clone()))).collect()
+    };
+    let ancestor_map = index_by_id(ancestor);
+    let local_map = index_by_id(local);
+    let cloud_map = index_by_id(cloud);
+
+    // Preserve ancestor ordering first, then append any ids that are new to
+    // local or cloud, in the order they first appear there.
+    let mut order: Vec<String> = ancestor.iter().filter_map(entity_id).collect();
+    for v in local.iter().chain(cloud.iter()) {
+        if let Some(id) = entity_id(v) {
+            if !order.contains(&id) {
+                order.push(id);
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+    for id in order {
+        let a = ancestor_map.get(&id);
+        let l = local_map.get(&id);
+        let c = cloud_map.get(&id);
+
+        match (l, c) {
+            (None, None) => {} // removed from both sides — drop
+            (Some(l), None) => result.push(l.clone()),
+            (None, Some(c)) => result.push(c.clone()),
+            (Some(l), Some(c)) if l == c => result.push(l.clone()),
+            (Some(l), Some(c)) => match a {
+                Some(a) => match three_way_merge(a, l, c, local_wins_ties) {
+                    MergeResult::Merged(v) => result.push(v),
+                    MergeResult::Conflict => return None,
+                },
+                // Same id independently created on both sides with no
+                // shared ancestor entry — no sensible merge, last-writer-wins.
+                None => result.push(if local_wins_ties { l.clone() } else { c.clone() }),
+            },
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn merged(result: MergeResult) -> Value {
+        match result {
+            MergeResult::Merged(v) => v,
+            MergeResult::Conflict => panic!("expected a merge, got a conflict"),
+        }
+    }
+
+    #[test]
+    fn unchanged_field_stays_as_is() {
+        let ancestor = json!({"title": "a"});
+        let local = json!({"title": "a"});
+        let cloud = json!({"title": "a"});
+        assert_eq!(merged(three_way_merge(&ancestor, &local, &cloud, true)), json!({"title": "a"}));
+    }
+
+    #[test]
+    fn field_changed_on_only_one_side_takes_that_side() {
+        let ancestor = json!({"title": "a"});
+        let local = json!({"title": "b"});
+        let cloud = json!({"title": "a"});
+        assert_eq!(merged(three_way_merge(&ancestor, &local, &cloud, true)), json!({"title": "b"}));
+
+        let local = json!({"title": "a"});
+        let cloud = json!({"title": "c"});
+        assert_eq!(merged(three_way_merge(&ancestor, &local, &cloud, true)), json!({"title": "c"}));
+    }
+
+    #[test]
+    fn object_keys_merge_union_wise() {
+        let ancestor = json!({"title": "a"});
+        let local = json!({"title": "a", "pinned": true});
+        let cloud = json!({"title": "a", "color": "red"});
+        assert_eq!(
+            merged(three_way_merge(&ancestor, &local, &cloud, true)),
+            json!({"title": "a", "pinned": true, "color": "red"})
+        );
+    }
+
+    #[test]
+    fn same_text_field_edited_differently_on_both_sides_is_a_conflict() {
+        let ancestor = json!({"body": "a"});
+        let local = json!({"body": "b"});
+        let cloud = json!({"body": "c"});
+        assert!(matches!(three_way_merge(&ancestor, &local, &cloud, true), MergeResult::Conflict));
+    }
+
+    #[test]
+    fn divergent_non_text_scalar_is_last_writer_wins_not_a_conflict() {
+        let ancestor = json!({"count": 1});
+        let local = json!({"count": 2});
+        let cloud = json!({"count": 3});
+        assert_eq!(merged(three_way_merge(&ancestor, &local, &cloud, true)), json!({"count": 2}));
+        assert_eq!(merged(three_way_merge(&ancestor, &local, &cloud, false)), json!({"count": 3}));
+    }
+
+    #[test]
+    fn entity_arrays_merge_as_add_wins_sets() {
+        let ancestor = json!([{"id": "1", "text": "a"}]);
+        let local = json!([{"id": "1", "text": "a"}, {"id": "2", "text": "new-local"}]);
+        let cloud = json!([{"id": "1", "text": "a"}, {"id": "3", "text": "new-cloud"}]);
+
+        assert_eq!(
+            merged(three_way_merge(&ancestor, &local, &cloud, true)),
+            json!([{"id": "1", "text": "a"}, {"id": "2", "text": "new-local"}, {"id": "3", "text": "new-cloud"}])
+        );
+    }
+
+    #[test]
+    fn entity_deleted_on_both_sides_is_dropped() {
+        let ancestor = json!([{"id": "1", "text": "a"}, {"id": "2", "text": "b"}]);
+        let local = json!([{"id": "1", "text": "a"}]);
+        let cloud = json!([{"id": "1", "text": "a"}]);
+        assert_eq!(merged(three_way_merge(&ancestor, &local, &cloud, true)), json!([{"id": "1", "text": "a"}]));
+    }
+
+    #[test]
+    fn entity_deleted_on_one_side_but_kept_on_the_other_survives() {
+        let ancestor = json!([{"id": "1", "text": "a"}]);
+        let local = json!([]);
+        let cloud = json!([{"id": "1", "text": "a"}]);
+        assert_eq!(merged(three_way_merge(&ancestor, &local, &cloud, true)), json!([{"id": "1", "text": "a"}]));
+    }
+}
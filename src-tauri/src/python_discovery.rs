@@ -0,0 +1,211 @@
+//! Interpreter discovery for the transcription install path.
+//!
+//! `prereflight_check` used to probe `py -3` or `python` and keep the first
+//! hit, with no way to tell whether it actually satisfies faster-whisper's
+//! version requirements or to offer alternatives. This module enumerates
+//! every candidate interpreter it can find (the Windows `py` launcher's full
+//! list, `pythonX.Y` on PATH on Unix, and any uv-managed interpreters) and
+//! ranks them against a version constraint so the UI can show — and the app
+//! can pick — a compatible one.
+
+use serde::Serialize;
+use std::process::Command;
+
+/// `major.minor`, with `minor: None` meaning "any minor acceptable" (used for
+/// candidates whose version we couldn't fully resolve).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct PythonVersion {
+    pub major: u8,
+    pub minor: Option<u8>,
+}
+
+impl PythonVersion {
+    fn satisfies_min(&self, major: u8, minor: u8) -> bool {
+        match self.minor {
+            Some(m) => (self.major, m) >= (major, minor),
+            None => self.major >= major,
+        }
+    }
+
+    fn satisfies_max_exclusive(&self, major: u8, minor: u8) -> bool {
+        match self.minor {
+            Some(m) => (self.major, m) < (major, minor),
+            None => self.major <= major,
+        }
+    }
+}
+
+/// One bound from a constraint string such as `">=3.9,<3.13"`.
+enum Bound {
+    Min(u8, u8),
+    MaxExclusive(u8, u8),
+}
+
+fn parse_bound(clause: &str) -> Option<Bound> {
+    let clause = clause.trim();
+    let (op, rest) = if let Some(rest) = clause.strip_prefix(">=") {
+        (">=", rest)
+    } else if let Some(rest) = clause.strip_prefix('<') {
+        ("<", rest)
+    } else {
+        return None;
+    };
+
+    let mut parts = rest.trim().splitn(2, '.');
+    let major: u8 = parts.next()?.parse().ok()?;
+    let minor: u8 = parts.next().unwrap_or("0").parse().ok()?;
+
+    match op {
+        ">=" => Some(Bound::Min(major, minor)),
+        "<" => Some(Bound::MaxExclusive(major, minor)),
+        _ => None,
+    }
+}
+
+/// Parse a comma-separated constraint like `">=3.9,<3.13"` into bounds, and
+/// return whether `version` satisfies all of them. An unparseable clause is
+/// ignored rather than treated as a hard failure, since a constraint string
+/// is advisory (we'd rather surface an imperfect candidate than none).
+pub fn version_satisfies(version: &PythonVersion, constraint: &str) -> bool {
+    constraint
+        .split(',')
+        .filter_map(parse_bound)
+        .all(|bound| match bound {
+            Bound::Min(major, minor) => version.satisfies_min(major, minor),
+            Bound::MaxExclusive(major, minor) => version.satisfies_max_exclusive(major, minor),
+        })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PythonCandidate {
+    pub executable: String,
+    pub version: Option<PythonVersion>,
+    pub raw_version: String,
+    /// Where this candidate came from: "py-launcher", "path", or "uv-managed".
+    pub source: String,
+    pub satisfies_constraint: bool,
+}
+
+/// A tiny probe script that prints the executable path and version tuple on
+/// separate lines, so we don't depend on parsing `sys.version`'s free-form text.
+const PROBE_SCRIPT: &str =
+    "import sys; print(sys.executable); print(sys.version_info[0]); print(sys.version_info[1] if len(sys.version_info) > 1 else '')";
+
+/// Run `cmd args... -c PROBE_SCRIPT` and parse its three lines. Handles
+/// Windows CRLF line endings via `str::lines`, which already splits on both.
+fn probe(cmd: &str, args: &[&str]) -> Option<(String, Option<PythonVersion>, String)> {
+    let mut full_args: Vec<&str> = args.to_vec();
+    full_args.push("-c");
+    full_args.push(PROBE_SCRIPT);
+
+    let mut command = Command::new(cmd);
+    command.args(&full_args);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines().map(|l| l.trim());
+    let executable = lines.next()?.to_string();
+    let major: Option<u8> = lines.next().and_then(|l| l.parse().ok());
+    let minor: Option<u8> = lines.next().and_then(|l| l.parse().ok());
+
+    let version = major.map(|major| PythonVersion { major, minor });
+    let raw_version = match version {
+        Some(PythonVersion { major, minor: Some(minor) }) => format!("{}.{}", major, minor),
+        Some(PythonVersion { major, minor: None }) => major.to_string(),
+        None => String::new(),
+    };
+
+    Some((executable, version, raw_version))
+}
+
+/// List every interpreter the Windows `py` launcher knows about via `py -0p`,
+/// whose output lines look like ` -V:3.11          C:\...\python.exe` or the
+/// older `-3.11-64        C:\...\python.exe` format.
+#[cfg(windows)]
+fn windows_py_launcher_executables() -> Vec<String> {
+    let Ok(output) = Command::new("py").arg("-0p").output() else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .filter(|token| token.to_ascii_lowercase().ends_with(".exe"))
+        .map(|s| s.to_string())
+        .collect()
+}
+
+#[cfg(not(windows))]
+fn windows_py_launcher_executables() -> Vec<String> {
+    Vec::new()
+}
+
+/// Interpreters managed by uv (`uv python install`), via `uv python list
+/// --only-installed`, whose output lines look like `cpython-3.11.9-linux-x86_64-gnu    /path/to/python`.
+fn uv_managed_executables() -> Vec<String> {
+    let Ok(output) = Command::new("uv").args(["python", "list", "--only-installed"]).output() else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Enumerate every candidate interpreter we can find and rank them against
+/// `constraint` (e.g. `">=3.9,<3.13"`). Candidates that satisfy the
+/// constraint sort first; within each group, higher versions sort first.
+pub fn discover_candidates(constraint: &str) -> Vec<PythonCandidate> {
+    let mut seen = std::collections::HashSet::new();
+    let mut candidates = Vec::new();
+
+    let mut add = |executable: String, source: &str| {
+        if !seen.insert(executable.clone()) {
+            return;
+        }
+        if let Some((resolved_exe, version, raw_version)) = probe(&executable, &[]) {
+            candidates.push(PythonCandidate {
+                executable: resolved_exe,
+                satisfies_constraint: version.map(|v| version_satisfies(&v, constraint)).unwrap_or(false),
+                version,
+                raw_version,
+                source: source.to_string(),
+            });
+        }
+    };
+
+    for exe in windows_py_launcher_executables() {
+        add(exe, "py-launcher");
+    }
+    for exe in uv_managed_executables() {
+        add(exe, "uv-managed");
+    }
+    for name in ["python3.13", "python3.12", "python3.11", "python3.10", "python3.9", "python3", "python"] {
+        add(name.to_string(), "path");
+    }
+
+    candidates.sort_by(|a, b| {
+        b.satisfies_constraint
+            .cmp(&a.satisfies_constraint)
+            .then_with(|| b.version.cmp(&a.version))
+    });
+
+    candidates
+}
@@ -0,0 +1,114 @@
+//! Persistent retry queue backing the legacy `sync_notes_to_google_drive`
+//! path. A network blip used to make that whole sync batch bail out and
+//! abandon whatever hadn't transferred yet. Now an upload/download that
+//! fails with a retriable error (HTTP 429/5xx, or the network being
+//! unreachable) is recorded here and queued with capped exponential
+//! backoff and jitter instead of being dropped, and an
+//! unreachable network pauses the whole queue so reconnecting resumes
+//! automatically on the next sync trigger rather than burning through
+//! retries while offline.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Manager};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncDirection {
+    Upload,
+    Download,
+}
+
+/// One pending transfer. `remote_id` is `None` for a new local file that
+/// hasn't been created on Drive yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedOp {
+    pub name: String,
+    pub direction: SyncDirection,
+    pub folder_id: String,
+    pub remote_id: Option<String>,
+    #[serde(default)]
+    pub retries: u32,
+}
+
+/// The durable queue plus pause state, round-tripped to `sync_retry_queue.json`
+/// in the Logia data dir so queued work survives an app restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetryQueue {
+    pub ops: Vec<QueuedOp>,
+    /// Set once an operation fails because the network itself looks
+    /// unreachable, so the worker stops spending retries until the queue is
+    /// drained again (e.g. the next time a sync is triggered) instead of
+    /// looping through backoff delays with no chance of succeeding.
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// Once an operation has failed this many times, it's dropped from the
+/// queue as a permanent failure rather than retried again.
+pub const MAX_QUEUE_RETRIES: u32 = 8;
+
+fn queue_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let logia_dir = app_handle
+        .path()
+        .resolve("Logia", BaseDirectory::Document)
+        .map_err(|_| "Could not resolve Logia directory".to_string())?;
+
+    if !logia_dir.exists() {
+        fs::create_dir_all(&logia_dir).map_err(|e| format!("Failed to create Logia dir: {}", e))?;
+    }
+
+    Ok(logia_dir.join("sync_retry_queue.json"))
+}
+
+/// Load the persisted queue; a missing or corrupt file just means an empty,
+/// unpaused queue rather than an error, since the queue is purely additive
+/// bookkeeping and losing it only means falling back to a full resync.
+pub fn load_queue(app_handle: &AppHandle) -> RetryQueue {
+    let Ok(path) = queue_path(app_handle) else { return RetryQueue::default() };
+    let Ok(content) = fs::read_to_string(&path) else { return RetryQueue::default() };
+    serde_json::from_str(&content).unwrap_or_default()
+}
+
+pub fn save_queue(app_handle: &AppHandle, queue: &RetryQueue) -> Result<(), String> {
+    let path = queue_path(app_handle)?;
+    let content = serde_json::to_string_pretty(queue).map_err(|e| format!("Failed to serialize retry queue: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write retry queue: {}", e))
+}
+
+/// Whether `error` looks like the network itself is down, as opposed to a
+/// transient server-side error — the two get different handling (pause the
+/// whole queue vs. just back off and retry the one operation).
+pub fn is_network_unreachable(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    lower.contains("connection refused")
+        || lower.contains("connection reset")
+        || lower.contains("dns")
+        || lower.contains("timed out")
+        || lower.contains("timeout")
+        || lower.contains("network is unreachable")
+        || lower.contains("could not connect")
+}
+
+/// Whether `error` is worth retrying at all: a rate limit, a server-side
+/// hiccup, or no network. Anything else (bad auth, a 404, a malformed
+/// request) is permanent and shouldn't eat retries.
+pub fn is_retriable(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    lower.contains("429")
+        || lower.contains("500")
+        || lower.contains("502")
+        || lower.contains("503")
+        || lower.contains("504")
+        || is_network_unreachable(error)
+}
+
+/// Backoff delay for a given retry count, with jitter so a burst of queued
+/// operations failing together doesn't all retry in lockstep.
+pub fn backoff_with_jitter(retries: u32) -> std::time::Duration {
+    let base_ms = 2u64.saturating_pow(retries.min(6)).min(60) * 1000;
+    let jitter_ms = (chrono::Utc::now().timestamp_millis() as u64) % 1000;
+    std::time::Duration::from_millis(base_ms + jitter_ms)
+}
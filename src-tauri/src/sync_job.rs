@@ -0,0 +1,119 @@
+//! Durable execution state for a `SyncPlan`.
+//!
+//! `build_sync_plan` only describes what needs to happen; nothing persists
+//! how far execution got. This module turns a plan into a sequence of
+//! `SyncTask`s with explicit state, and round-trips the whole job to
+//! `sync_job.msgpack` next to `sync_manifest.json` so a sync interrupted by
+//! a quit (or an explicit pause) can resume from the first unfinished task
+//! instead of recomputing, and possibly re-transferring, everything.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Manager};
+
+use crate::sync_manifest::{SyncAction, SyncPlan};
+
+/// How far along a single planned action is. `Failed` carries the retry
+/// count so the execution loop can apply capped exponential backoff and
+/// give up after enough attempts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum TaskState {
+    Pending,
+    InFlight,
+    Done,
+    Failed { retries: u32 },
+}
+
+/// One durable step of a sync job. `byte_offset`/`chunk_index` are stored
+/// with the task, but execution does not read or advance them, so an
+/// interrupted transfer restarts from the start of the file.
+/// `cloud_file_id` is recorded by the executor for downloads; an upload uses
+/// it when set and otherwise the manifest's id for the file, and does not
+/// record the id of a file it creates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncTask {
+    pub action: SyncAction,
+    pub state: TaskState,
+    pub byte_offset: usize,
+    pub chunk_index: usize,
+    pub cloud_file_id: Option<String>,
+}
+
+impl SyncTask {
+    fn pending(action: SyncAction) -> Self {
+        Self { action, state: TaskState::Pending, byte_offset: 0, chunk_index: 0, cloud_file_id: None }
+    }
+}
+
+/// A sync job: the durable task list plus enough bookkeeping to resume it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncJob {
+    pub tasks: Vec<SyncTask>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Maximum retries for a failed task before it's surfaced to the caller as
+/// a permanent failure instead of retried again.
+pub const MAX_RETRIES: u32 = 5;
+
+/// Build a fresh job from a plan: every upload/download/deletion becomes
+/// one `Pending` task. Conflicts are left out — they need a resolution
+/// before they can become an action at all (see `execute_sync_with_resolutions`).
+pub fn job_from_plan(plan: &SyncPlan) -> SyncJob {
+    let tasks = plan.uploads.iter()
+        .chain(plan.downloads.iter())
+        .chain(plan.deletions_local.iter())
+        .chain(plan.deletions_cloud.iter())
+        .cloned()
+        .map(SyncTask::pending)
+        .collect();
+
+    SyncJob { tasks, created_at: Utc::now() }
+}
+
+fn sync_job_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let logia_dir = app_handle
+        .path()
+        .resolve("Logia", BaseDirectory::Document)
+        .map_err(|_| "Could not resolve Logia directory".to_string())?;
+
+    if !logia_dir.exists() {
+        fs::create_dir_all(&logia_dir).map_err(|e| format!("Failed to create Logia dir: {}", e))?;
+    }
+
+    Ok(logia_dir.join("sync_job.msgpack"))
+}
+
+/// Load the persisted job, if a sync was left paused or interrupted.
+pub fn load_sync_job(app_handle: &AppHandle) -> Result<Option<SyncJob>, String> {
+    let path = sync_job_path(app_handle)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let bytes = fs::read(&path).map_err(|e| format!("Failed to read sync job: {}", e))?;
+    let job: SyncJob = rmp_serde::from_slice(&bytes).map_err(|e| format!("Failed to parse sync job: {}", e))?;
+    Ok(Some(job))
+}
+
+/// Persist `job`, called after every task state transition so the file on
+/// disk is never more than one task behind reality.
+pub fn save_sync_job(app_handle: &AppHandle, job: &SyncJob) -> Result<(), String> {
+    let path = sync_job_path(app_handle)?;
+    let bytes = rmp_serde::to_vec(job).map_err(|e| format!("Failed to serialize sync job: {}", e))?;
+    fs::write(&path, bytes).map_err(|e| format!("Failed to write sync job: {}", e))
+}
+
+/// Remove the job file once every task has completed; there's nothing left
+/// to resume.
+pub fn clear_sync_job(app_handle: &AppHandle) -> Result<(), String> {
+    let path = sync_job_path(app_handle)?;
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to remove sync job: {}", e))?;
+    }
+    Ok(())
+}
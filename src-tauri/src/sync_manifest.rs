@@ -21,6 +21,84 @@ pub enum FileStatus {
     NewCloud,         // New cloud file, not locally
 }
 
+/// Whether a `SyncFilterRule` pulls a matching path into the sync, or keeps
+/// it out.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    Include,
+    Exclude,
+}
+
+/// One include/exclude rule scoping what `sync_directory`/`scan_local_files`/
+/// `update_manifest_from_cloud` treat as synced at all, matched against a
+/// file's relative `subdir/name` path (see `is_path_included`). Stored
+/// ordered on `DriveConfig::sync_filters` in `google_drive.rs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncFilterRule {
+    pub action: FilterAction,
+    pub pattern: String,
+}
+
+/// Glob supporting `*` (any run of characters, including `/`) and `?`
+/// (exactly one character). Used both for whole relative paths here and,
+/// via `google_drive::is_ignored`, for bare `.logiaignore` file names — the
+/// algorithm doesn't care which, since `*` already matches `/`.
+pub(crate) fn path_glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let (mut star_pi, mut star_ti) = (None, 0);
+
+    while ti < text.len() {
+        if pi < pattern.len() && (pattern[pi] == '?' || pattern[pi] == text[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < pattern.len() && pattern[pi] == '*' {
+            star_pi = Some(pi);
+            star_ti = ti;
+            pi += 1;
+        } else if let Some(sp) = star_pi {
+            pi = sp + 1;
+            star_ti += 1;
+            ti = star_ti;
+        } else {
+            return false;
+        }
+    }
+
+    while pi < pattern.len() && pattern[pi] == '*' {
+        pi += 1;
+    }
+
+    pi == pattern.len()
+}
+
+/// Whether `relative_path` (a `subdir/name` path) should be synced at all,
+/// per `rules` evaluated in order with last-match-wins semantics — mirroring
+/// how backup sync jobs track include/exclude group filters. A path matching
+/// no rule is included by default.
+pub fn is_path_included(relative_path: &str, rules: &[SyncFilterRule]) -> bool {
+    let mut included = true;
+    for rule in rules {
+        if path_glob_match(&rule.pattern, relative_path) {
+            included = rule.action == FilterAction::Include;
+        }
+    }
+    included
+}
+
+/// One content-defined chunk of a file: a byte range plus the SHA256
+/// "strong hash" of that range, used to detect which chunks a file shares
+/// with a previous version (or with other files) so only new chunks need to
+/// cross the wire. See `chunk_file_contents` for how boundaries are chosen.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SyncChunk {
+    pub offset: usize,
+    pub len: usize,
+    pub strong_hash: String,
+}
+
 /// State of a single file in the manifest
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileState {
@@ -30,6 +108,29 @@ pub struct FileState {
     pub cloud_modified: Option<DateTime<Utc>>,
     pub status: FileStatus,
     pub cloud_file_id: Option<String>,  // Google Drive file ID
+    /// Chunk layout of the file as of `local_hash`. Recomputed only when
+    /// `local_hash` changes, not on every scan.
+    #[serde(default)]
+    pub chunks: Vec<SyncChunk>,
+    /// Hash both sides agreed on as of the last time this file reached
+    /// `Synced`, i.e. the common ancestor for three-way merging. Unlike
+    /// `cloud_hash` (which a fresh `update_manifest_from_cloud` call
+    /// overwrites with whatever's on Drive *right now*, conflict or not),
+    /// this only ever changes when a sync actually resolves the file, so
+    /// it's still around to diff against once both sides have moved on.
+    #[serde(default)]
+    pub base_hash: Option<String>,
+    /// Algorithm `local_hash`/`cloud_hash`/`base_hash` were computed with.
+    /// Every hash in this manifest is MD5 today, but stamping it per-file
+    /// rather than assuming it means a future switch (or a manifest written
+    /// by an older build) can't silently compare hashes computed two
+    /// different ways.
+    #[serde(default = "default_hash_algo")]
+    pub hash_algo: String,
+}
+
+pub(crate) fn default_hash_algo() -> String {
+    "md5".to_string()
 }
 
 impl Default for FileState {
@@ -41,6 +142,9 @@ impl Default for FileState {
             cloud_modified: None,
             status: FileStatus::Synced,
             cloud_file_id: None,
+            chunks: Vec::new(),
+            base_hash: None,
+            hash_algo: default_hash_algo(),
         }
     }
 }
@@ -52,6 +156,12 @@ pub struct SyncManifest {
     pub device_id: String,
     pub last_sync: Option<DateTime<Utc>>,
     pub files: HashMap<String, FileState>,
+    /// Strong hashes of every chunk already confirmed present in the cloud
+    /// chunk store, across every file. Consulted before uploading any chunk
+    /// so identical content shared between files (or between revisions of
+    /// the same file) is only ever uploaded once.
+    #[serde(default)]
+    pub known_chunk_hashes: std::collections::HashSet<String>,
 }
 
 impl Default for SyncManifest {
@@ -61,6 +171,7 @@ impl Default for SyncManifest {
             device_id: uuid::Uuid::new_v4().to_string(),
             last_sync: None,
             files: HashMap::new(),
+            known_chunk_hashes: std::collections::HashSet::new(),
         }
     }
 }
@@ -82,6 +193,12 @@ pub struct SyncPlan {
     pub conflicts: Vec<SyncAction>,    // Files that need user resolution
     pub deletions_local: Vec<SyncAction>,   // Delete locally (file deleted in cloud)
     pub deletions_cloud: Vec<SyncAction>,   // Delete from cloud (file deleted locally)
+    /// Paths of `"<name>.conflict-<timestamp>"` sibling files created this
+    /// call because a conflict couldn't be merged automatically — the local
+    /// file under `conflicts` kept its own content, and the cloud edit was
+    /// saved here instead of being silently discarded.
+    #[serde(default)]
+    pub conflict_siblings: Vec<String>,
 }
 
 impl Default for SyncPlan {
@@ -92,6 +209,7 @@ impl Default for SyncPlan {
             conflicts: Vec::new(),
             deletions_local: Vec::new(),
             deletions_cloud: Vec::new(),
+            conflict_siblings: Vec::new(),
         }
     }
 }
@@ -118,13 +236,137 @@ impl SyncPlan {
     }
 }
 
-/// Compute SHA256 hash of file contents
+/// MD5 of a file's contents, in the same lowercase-hex form Drive reports in
+/// `md5Checksum`, so a file's `local_hash`/`cloud_hash` can be compared
+/// directly against it instead of going through a separate translation step.
 pub fn compute_file_hash(path: &PathBuf) -> Result<String, String> {
     let contents = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
-    let mut hasher = Sha256::new();
-    hasher.update(&contents);
-    let result = hasher.finalize();
-    Ok(format!("{:x}", result))
+    Ok(format!("{:x}", md5::compute(&contents)))
+}
+
+const CHUNK_WINDOW_SIZE: usize = 48;
+/// Average chunk size is `2^CHUNK_MASK_BITS` bytes.
+const CHUNK_MASK_BITS: u32 = 13;
+const CHUNK_MIN_SIZE: usize = 2 * 1024;
+const CHUNK_MAX_SIZE: usize = 64 * 1024;
+
+/// A fixed, app-wide table of per-byte values for the buzhash rolling
+/// window. Deterministic so chunk boundaries are reproducible across runs
+/// and machines syncing the same content.
+fn chunk_hash_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut state: u32 = 0x9e3779b9;
+    for (i, slot) in table.iter_mut().enumerate() {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state = state.wrapping_add(i as u32);
+        *slot = state;
+    }
+    table
+}
+
+/// Split `data` into content-defined chunks, returning `(offset, len)` pairs
+/// covering the whole input. A boundary falls wherever the low
+/// `CHUNK_MASK_BITS` bits of the rolling buzhash are zero, clamped to
+/// `CHUNK_MIN_SIZE`/`CHUNK_MAX_SIZE` so pathological inputs (all-zero runs,
+/// huge files with no natural boundary) stay bounded.
+fn chunk_boundaries(data: &[u8]) -> Vec<(usize, usize)> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let table = chunk_hash_table();
+    let mask = (1u32 << CHUNK_MASK_BITS) - 1;
+    let rot = (CHUNK_WINDOW_SIZE % 32) as u32;
+
+    let mut boundaries = Vec::new();
+    let mut start = 0usize;
+    let mut hash: u32 = 0;
+
+    for i in 0..data.len() {
+        let incoming = table[data[i] as usize];
+        hash = hash.rotate_left(1) ^ incoming;
+
+        let window_len = i - start + 1;
+        if window_len > CHUNK_WINDOW_SIZE {
+            let outgoing = table[data[i - CHUNK_WINDOW_SIZE] as usize];
+            hash ^= outgoing.rotate_left(rot);
+        }
+
+        let chunk_len = i - start + 1;
+        let at_boundary = chunk_len >= CHUNK_MIN_SIZE && (hash & mask) == 0;
+        let forced = chunk_len >= CHUNK_MAX_SIZE;
+
+        if at_boundary || forced || i == data.len() - 1 {
+            boundaries.push((start, chunk_len));
+            start = i + 1;
+            hash = 0;
+        }
+    }
+
+    boundaries
+}
+
+/// Split `data` into content-defined chunks and hash each one, producing the
+/// ordered chunk list stored on a `FileState`.
+pub fn chunk_file_contents(data: &[u8]) -> Vec<SyncChunk> {
+    chunk_boundaries(data)
+        .into_iter()
+        .map(|(offset, len)| {
+            let mut hasher = Sha256::new();
+            hasher.update(&data[offset..offset + len]);
+            SyncChunk { offset, len, strong_hash: format!("{:x}", hasher.finalize()) }
+        })
+        .collect()
+}
+
+/// Read `path` and chunk its contents. Used as the fast path's follow-up
+/// once a whole-file hash comparison has already shown the file changed.
+pub fn chunk_file(path: &PathBuf) -> Result<Vec<SyncChunk>, String> {
+    let contents = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    Ok(chunk_file_contents(&contents))
+}
+
+fn ancestor_store_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let logia_dir = app_handle
+        .path()
+        .resolve("Logia", BaseDirectory::Document)
+        .map_err(|_| "Could not resolve Logia directory".to_string())?;
+
+    let dir = logia_dir.join(".sync_ancestors");
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create ancestor store: {}", e))?;
+    }
+    Ok(dir)
+}
+
+/// Shard ancestor blobs by their first two hex chars, same convention as
+/// `backup.rs`'s chunk store, so the directory doesn't accumulate one huge
+/// flat listing of files.
+fn ancestor_path(app_handle: &tauri::AppHandle, hash: &str) -> Result<PathBuf, String> {
+    let dir = ancestor_store_dir(app_handle)?;
+    let shard = dir.join(&hash[..hash.len().min(2)]);
+    if !shard.exists() {
+        fs::create_dir_all(&shard).map_err(|e| format!("Failed to create ancestor shard: {}", e))?;
+    }
+    Ok(shard.join(hash))
+}
+
+/// Cache the content behind a just-synced file's hash, so that if a future
+/// edit on both sides conflicts, there's a common ancestor to three-way
+/// merge against even after both sides have since moved past it. Best
+/// effort: a write failure here just means a future conflict on this file
+/// can't be auto-merged and falls back to manual resolution.
+pub fn save_ancestor_content(app_handle: &tauri::AppHandle, hash: &str, content: &[u8]) -> Result<(), String> {
+    let path = ancestor_path(app_handle, hash)?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write ancestor content: {}", e))
+}
+
+/// Load previously cached ancestor content for `hash`, if any.
+pub fn load_ancestor_content(app_handle: &tauri::AppHandle, hash: &str) -> Option<Vec<u8>> {
+    let path = ancestor_path(app_handle, hash).ok()?;
+    fs::read(&path).ok()
 }
 
 /// Get the path to the local manifest file
@@ -133,92 +375,221 @@ pub fn get_manifest_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, Strin
         .path()
         .resolve("Logia", BaseDirectory::Document)
         .map_err(|_| "Could not resolve Logia directory")?;
-    
+
     if !logia_dir.exists() {
         fs::create_dir_all(&logia_dir).map_err(|e| format!("Failed to create Logia dir: {}", e))?;
     }
-    
+
     Ok(logia_dir.join("sync_manifest.json"))
 }
 
-/// Load the local manifest (or create a new one if it doesn't exist)
+fn manifest_tmp_path(manifest_path: &std::path::Path) -> PathBuf {
+    manifest_path.with_extension("json.tmp")
+}
+
+fn manifest_bak_path(manifest_path: &std::path::Path) -> PathBuf {
+    manifest_path.with_extension("json.bak")
+}
+
+/// Write-ahead journal path: a sync job writes the manifest here before it
+/// starts mutating cloud file IDs, so an interrupted job leaves behind a
+/// record of the state it started from.
+fn manifest_journal_path(manifest_path: &std::path::Path) -> PathBuf {
+    manifest_path.with_extension("json.journal")
+}
+
+/// Write `content` fully, `fsync` it, then return — used for every manifest
+/// artifact (tmp file, journal) so a crash can't leave a half-written file
+/// behind for recovery logic to trip over.
+fn write_and_sync(path: &std::path::Path, content: &str) -> Result<(), String> {
+    use std::io::Write;
+    let mut file = fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    file.write_all(content.as_bytes()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    file.sync_all().map_err(|e| format!("Failed to fsync {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+fn parse_manifest_file(path: &std::path::Path) -> Option<SyncManifest> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Load the local manifest (or create a new one if it doesn't exist),
+/// recovering automatically from a crash that interrupted a previous save:
+///
+/// - A leftover `.tmp` file means `save_local_manifest` wrote the new
+///   content and fsynced it, but the crash happened before the rename into
+///   place — the tmp file is itself a complete, valid manifest, so it's
+///   promoted directly.
+/// - A real manifest that's missing or fails to parse falls back to the
+///   last rotated `.bak` copy, and failing that, the write-ahead `.journal`
+///   left by an interrupted sync job.
 pub fn load_local_manifest(app_handle: &tauri::AppHandle) -> Result<SyncManifest, String> {
     let path = get_manifest_path(app_handle)?;
-    
+    let tmp_path = manifest_tmp_path(&path);
+    let bak_path = manifest_bak_path(&path);
+    let journal_path = manifest_journal_path(&path);
+
+    if tmp_path.exists() {
+        if let Some(manifest) = parse_manifest_file(&tmp_path) {
+            log::warn!("Recovered sync manifest from interrupted save (tmp file)");
+            fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to promote recovered manifest: {}", e))?;
+            let _ = fs::remove_file(&journal_path);
+            return Ok(manifest);
+        }
+        // Tmp file itself is corrupt (crash mid-write, before fsync landed) — discard it.
+        let _ = fs::remove_file(&tmp_path);
+    }
+
     if path.exists() {
-        let content = fs::read_to_string(&path)
-            .map_err(|e| format!("Failed to read manifest: {}", e))?;
-        serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse manifest: {}", e))
-    } else {
-        Ok(SyncManifest::default())
+        match parse_manifest_file(&path) {
+            Some(manifest) => {
+                // The manifest parsed, so the journal isn't needed for recovery.
+                let _ = fs::remove_file(&journal_path);
+                return Ok(manifest);
+            }
+            None => {
+                log::warn!("Sync manifest failed to parse, attempting recovery");
+            }
+        }
     }
+
+    if let Some(manifest) = parse_manifest_file(&bak_path) {
+        log::warn!("Recovered sync manifest from backup copy");
+        return Ok(manifest);
+    }
+
+    if let Some(manifest) = parse_manifest_file(&journal_path) {
+        log::warn!("Recovered sync manifest from write-ahead journal");
+        return Ok(manifest);
+    }
+
+    Ok(SyncManifest::default())
 }
 
-/// Save the manifest to local disk
+/// Save the manifest to local disk atomically: write the full content to a
+/// sibling `.tmp` file and fsync it, rotate the previous manifest into
+/// `.bak`, then `rename` the tmp file over the real path. Readers never see
+/// a partially-written document, and a crash between the fsync and the
+/// rename just leaves the complete tmp file for `load_local_manifest` to
+/// pick up.
 pub fn save_local_manifest(app_handle: &tauri::AppHandle, manifest: &SyncManifest) -> Result<(), String> {
     let path = get_manifest_path(app_handle)?;
+    let tmp_path = manifest_tmp_path(&path);
+    let bak_path = manifest_bak_path(&path);
+
     let content = serde_json::to_string_pretty(manifest)
         .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
-    fs::write(&path, content)
-        .map_err(|e| format!("Failed to write manifest: {}", e))
+
+    write_and_sync(&tmp_path, &content)?;
+
+    if path.exists() {
+        let _ = fs::copy(&path, &bak_path);
+    }
+
+    fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to commit manifest: {}", e))?;
+
+    // The save completed, so any write-ahead journal from the job that just
+    // finished is no longer needed.
+    let _ = fs::remove_file(manifest_journal_path(&path));
+
+    Ok(())
 }
 
-/// Scan all local files and compute their current state
-pub fn scan_local_files(app_handle: &tauri::AppHandle) -> Result<HashMap<String, (String, DateTime<Utc>)>, String> {
+/// Record `manifest` as the write-ahead journal before a sync job starts
+/// mutating cloud file IDs. If the job is interrupted before its final
+/// `save_local_manifest` call, `load_local_manifest` can fall back to this
+/// as a last-consistent-state recovery instead of surfacing a parse error.
+pub fn journal_manifest_state(app_handle: &tauri::AppHandle, manifest: &SyncManifest) -> Result<(), String> {
+    let path = get_manifest_path(app_handle)?;
+    let journal_path = manifest_journal_path(&path);
+    let content = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize manifest journal: {}", e))?;
+    write_and_sync(&journal_path, &content)
+}
+
+/// Scan all local files and compute their current state. The `PathBuf` is
+/// the absolute path, kept alongside the hash so callers that detect a
+/// change can re-chunk the file without re-resolving its location.
+///
+/// `manifest` is consulted purely as a cheap pre-filter: a file whose mtime
+/// still matches `local_modified` from the last scan reuses the manifest's
+/// recorded `local_hash` instead of rereading and rehashing its contents,
+/// since content can't have changed without the mtime also changing.
+pub fn scan_local_files(app_handle: &tauri::AppHandle, manifest: &SyncManifest, filters: &[SyncFilterRule]) -> Result<HashMap<String, (String, DateTime<Utc>, PathBuf)>, String> {
     let mut files = HashMap::new();
-    
+
     let subdirs = ["notes", "folders", "kanban", "trash"];
-    
+
     for subdir in subdirs {
         let dir_path = app_handle
             .path()
             .resolve(&format!("Logia/{}", subdir), BaseDirectory::Document)
             .map_err(|_| format!("Could not resolve {} directory", subdir))?;
-        
+
         if !dir_path.exists() {
             continue;
         }
-        
+
         if let Ok(entries) = fs::read_dir(&dir_path) {
             for entry in entries.flatten() {
                 let path = entry.path();
                 if path.extension().and_then(|s| s.to_str()) == Some("json") {
                     let filename = entry.file_name().to_string_lossy().to_string();
                     let rel_path = format!("{}/{}", subdir, filename);
-                    
-                    if let Ok(hash) = compute_file_hash(&path) {
-                        if let Ok(metadata) = fs::metadata(&path) {
-                            if let Ok(modified) = metadata.modified() {
-                                let modified_dt = DateTime::<Utc>::from(modified);
-                                files.insert(rel_path, (hash, modified_dt));
-                            }
-                        }
+
+                    if !is_path_included(&rel_path, filters) {
+                        continue;
                     }
+
+                    let Ok(metadata) = fs::metadata(&path) else { continue };
+                    let Ok(modified) = metadata.modified() else { continue };
+                    let modified_dt = DateTime::<Utc>::from(modified);
+
+                    let unchanged_hash = manifest.files.get(&rel_path).and_then(|state| {
+                        if state.local_modified == Some(modified_dt) {
+                            state.local_hash.clone()
+                        } else {
+                            None
+                        }
+                    });
+
+                    let hash = match unchanged_hash {
+                        Some(hash) => hash,
+                        None => match compute_file_hash(&path) {
+                            Ok(hash) => hash,
+                            Err(_) => continue,
+                        },
+                    };
+
+                    files.insert(rel_path, (hash, modified_dt, path.clone()));
                 }
             }
         }
     }
-    
+
     Ok(files)
 }
 
 /// Compare local state against manifest to find changes
 pub fn detect_local_changes(
     manifest: &SyncManifest,
-    local_files: &HashMap<String, (String, DateTime<Utc>)>,
+    local_files: &HashMap<String, (String, DateTime<Utc>, PathBuf)>,
 ) -> SyncManifest {
     let mut updated_manifest = manifest.clone();
-    
+
     // Check each local file
-    for (path, (hash, modified)) in local_files {
+    for (path, (hash, modified, abs_path)) in local_files {
         if let Some(state) = updated_manifest.files.get_mut(path) {
             // File exists in manifest - check if changed
             if state.local_hash.as_ref() != Some(hash) {
-                // Local file changed
+                // Local file changed. The whole-file hash is the fast-path
+                // identity check; only recompute the (more expensive) chunk
+                // list once we know it's actually needed.
                 state.local_hash = Some(hash.clone());
                 state.local_modified = Some(*modified);
-                
+                state.chunks = chunk_file(abs_path).unwrap_or_default();
+
                 // Determine new status
                 if state.status == FileStatus::Synced {
                     state.status = FileStatus::LocalModified;
@@ -235,6 +606,9 @@ pub fn detect_local_changes(
                 cloud_modified: None,
                 status: FileStatus::NewLocal,
                 cloud_file_id: None,
+                chunks: chunk_file(abs_path).unwrap_or_default(),
+                base_hash: None,
+                hash_algo: default_hash_algo(),
             });
         }
     }
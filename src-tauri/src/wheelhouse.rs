@@ -0,0 +1,135 @@
+//! Offline installs: a bundled per-platform wheelhouse of prebuilt wheels
+//! (faster-whisper, av, ctranslate2, and their transitive deps), so
+//! `ensure_transcription_dependencies` has a way forward on machines that
+//! can't reach PyPI. When it looks complete for the detected interpreter its
+//! wheels are installed natively via `wheel_installer`, with uv/pip pointed at
+//! the bundled directory (`--find-links <dir> --no-index`) if that fails;
+//! otherwise we fall back to the normal network install.
+
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use tauri::path::BaseDirectory;
+
+use crate::error::{KortexError, KortexResult};
+use crate::wheel_installer::WheelName;
+
+/// Resolve the bundled wheelhouse resource directory. It doesn't necessarily
+/// exist (older builds, or platforms we haven't populated a wheelhouse for
+/// yet), which just means every check below reports those wheels missing.
+pub fn wheelhouse_dir(app_handle: &tauri::AppHandle) -> KortexResult<PathBuf> {
+    app_handle
+        .path()
+        .resolve("src/audio/transcription/wheelhouse", BaseDirectory::Resource)
+        .map_err(|e| KortexError::PathResolution(format!("Could not resolve wheelhouse resource: {}", e)))
+}
+
+/// Best-effort mapping from a `requirements.txt` line to a bare package name
+/// (drop version specifiers, extras, environment markers, and comments).
+fn requirement_package_name(line: &str) -> Option<String> {
+    let line = line.split('#').next().unwrap_or("").trim();
+    if line.is_empty() {
+        return None;
+    }
+    let name = line
+        .split(|c: char| "<>=!~;[ ".contains(c))
+        .next()
+        .unwrap_or("")
+        .trim();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+fn normalize_distribution_name(name: &str) -> String {
+    name.to_ascii_lowercase().replace(['-', '_', '.'], "")
+}
+
+/// Loose platform-family match. Bundled wheel tags like `manylinux2014_x86_64`
+/// and the reported `manylinux_2_17_x86_64` alias both just mean "linux" for
+/// our purposes here — the strict tag check happens for real at install time
+/// in `wheel_installer::install_wheel`.
+fn platform_family(tag: &str) -> &'static str {
+    let tag = tag.to_ascii_lowercase();
+    if tag.contains("win") {
+        "windows"
+    } else if tag.contains("macosx") {
+        "macos"
+    } else if tag.contains("linux") {
+        "linux"
+    } else {
+        "any"
+    }
+}
+
+fn current_platform_family() -> &'static str {
+    match std::env::consts::OS {
+        "windows" => "windows",
+        "macos" => "macos",
+        "linux" => "linux",
+        _ => "any",
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WheelhouseReport {
+    pub present: Vec<String>,
+    pub missing: Vec<String>,
+    /// True only when every requirement has a matching bundled wheel — i.e.
+    /// it's safe to install fully offline with `--no-index`.
+    pub complete: bool,
+}
+
+/// List wheels bundled for the current platform and report which of
+/// `requirements` (raw `requirements.txt` lines) have a matching,
+/// tag-compatible wheel present for `python_tag` (e.g. `"cp311"`).
+pub fn check_wheelhouse(
+    app_handle: &tauri::AppHandle,
+    requirements: &[String],
+    python_tag: &str,
+) -> KortexResult<WheelhouseReport> {
+    let dir = wheelhouse_dir(app_handle)?;
+    let target_family = current_platform_family();
+
+    let bundled: Vec<WheelName> = if dir.exists() {
+        fs::read_dir(&dir)?
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().and_then(|s| s.to_str()) == Some("whl") {
+                    WheelName::parse(&path).ok()
+                } else {
+                    None
+                }
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut present = Vec::new();
+    let mut missing = Vec::new();
+
+    for req_line in requirements {
+        let Some(package) = requirement_package_name(req_line) else { continue };
+        let normalized = normalize_distribution_name(&package);
+
+        let has_match = bundled.iter().any(|wheel| {
+            normalize_distribution_name(&wheel.distribution) == normalized
+                && platform_family(&wheel.platform_tag) == target_family
+                && (wheel.python_tag.split('.').any(|t| t == python_tag)
+                    || wheel.python_tag.starts_with("py")
+                    || wheel.abi_tag == "none")
+        });
+
+        if has_match {
+            present.push(package);
+        } else {
+            missing.push(package);
+        }
+    }
+
+    Ok(WheelhouseReport { complete: missing.is_empty() && !present.is_empty(), present, missing })
+}